@@ -0,0 +1,159 @@
+use crate::types::Measurement;
+use fixed::types::I16F16;
+
+/// Members of the SHT4x family differing in their specified accuracy.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Variant {
+    Sht40,
+    Sht41,
+    Sht43,
+    Sht45,
+}
+
+/// Symmetric tolerance of a measurement.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Accuracy {
+    /// The temperature tolerance in degree Celsius (±°C).
+    pub temperature: I16F16,
+    /// The relative humidity tolerance in percent (±%).
+    pub humidity: I16F16,
+}
+
+/// Specified accuracy of a sensor variant at a measured point.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AccuracyBand {
+    /// The typical tolerance.
+    pub typical: Accuracy,
+    /// The maximum tolerance.
+    pub maximum: Accuracy,
+}
+
+/// A support point of an accuracy plot: measured value, typical and maximum tolerance, all in
+/// thousandths of the respective unit.
+type SupportPoint = (i32, i32, i32);
+
+/// Tolerance plots for a single variant.
+struct Tolerances {
+    temperature: &'static [SupportPoint],
+    humidity: &'static [SupportPoint],
+}
+
+// The support points are read off the accuracy plots in the
+// [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf),
+// section 2 _Sensor Performance_. The humidity plots are specified at 25 °C.
+const SHT40: Tolerances = Tolerances {
+    temperature: &[
+        (-40_000, 400, 800),
+        (0, 200, 400),
+        (65_000, 200, 400),
+        (90_000, 300, 600),
+        (125_000, 500, 1000),
+    ],
+    humidity: &[
+        (0, 2500, 3500),
+        (10_000, 1800, 3500),
+        (90_000, 1800, 3500),
+        (100_000, 2500, 4000),
+    ],
+};
+
+const SHT41: Tolerances = Tolerances {
+    temperature: &[
+        (-40_000, 400, 600),
+        (0, 200, 300),
+        (65_000, 200, 300),
+        (90_000, 200, 400),
+        (125_000, 400, 700),
+    ],
+    humidity: &[
+        (0, 2500, 3000),
+        (10_000, 1800, 2500),
+        (90_000, 1800, 2500),
+        (100_000, 2500, 3500),
+    ],
+};
+
+const SHT45: Tolerances = Tolerances {
+    temperature: &[
+        (-40_000, 300, 500),
+        (0, 100, 200),
+        (65_000, 100, 200),
+        (90_000, 200, 400),
+        (125_000, 400, 700),
+    ],
+    humidity: &[
+        (0, 1500, 2000),
+        (10_000, 1000, 1500),
+        (90_000, 1000, 1500),
+        (100_000, 1500, 2000),
+    ],
+};
+
+impl Variant {
+    /// Returns the specified accuracy of this variant at the given measurement.
+    ///
+    /// The tolerances get linearly interpolated between support points read off the accuracy
+    /// plots in the datasheet and are clamped to the specified range. The humidity tolerances are
+    /// specified at 25 °C and do not account for the temperature.
+    pub fn accuracy(&self, measurement: &Measurement) -> AccuracyBand {
+        let tolerances = match self {
+            Variant::Sht40 => &SHT40,
+            // The SHT43 is specified like the SHT41.
+            Variant::Sht41 | Variant::Sht43 => &SHT41,
+            Variant::Sht45 => &SHT45,
+        };
+
+        let (temperature_typical, temperature_maximum) = interpolate(
+            tolerances.temperature,
+            measurement.temperature_milli_celsius(),
+        );
+        let (humidity_typical, humidity_maximum) =
+            interpolate(tolerances.humidity, measurement.humidity_milli_percent());
+
+        AccuracyBand {
+            typical: Accuracy {
+                temperature: from_milli(temperature_typical),
+                humidity: from_milli(humidity_typical),
+            },
+            maximum: Accuracy {
+                temperature: from_milli(temperature_maximum),
+                humidity: from_milli(humidity_maximum),
+            },
+        }
+    }
+}
+
+impl Measurement {
+    /// Returns the specified accuracy of the given sensor variant at this measurement.
+    ///
+    /// See [`Variant::accuracy`] for details.
+    pub fn accuracy(&self, variant: Variant) -> AccuracyBand {
+        variant.accuracy(self)
+    }
+}
+
+fn from_milli(value: i32) -> I16F16 {
+    I16F16::from_num(value) / 1000
+}
+
+fn interpolate(points: &[SupportPoint], value: i32) -> (i32, i32) {
+    let (first, last) = (points[0], points[points.len() - 1]);
+    if value <= first.0 {
+        return (first.1, first.2);
+    }
+
+    points
+        .windows(2)
+        .find(|window| value <= window[1].0)
+        .map(|window| {
+            let ((x0, typical0, maximum0), (x1, typical1, maximum1)) = (window[0], window[1]);
+            let lerp = |y0: i32, y1: i32| {
+                let offset = i64::from(y1 - y0) * i64::from(value - x0) / i64::from(x1 - x0);
+                y0 + offset as i32
+            };
+            (lerp(typical0, typical1), lerp(maximum0, maximum1))
+        })
+        .unwrap_or((last.1, last.2))
+}
@@ -0,0 +1,158 @@
+use crate::{
+    error::Error,
+    sht4x::Sht4x,
+    types::{Address, Measurement, Precision, SensorAddress},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use fixed::types::{I16F16, I48F16};
+
+/// Configuration for [`AdaptiveSampler`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AdaptiveConfig {
+    /// The time between two measurements in milliseconds while readings are stable.
+    pub slow_interval_ms: u32,
+    /// The time between two measurements in milliseconds while readings are changing.
+    pub fast_interval_ms: u32,
+    /// The temperature rate of change in degree Celsius per minute (°C/min) above which readings
+    /// are considered changing.
+    pub temperature_rate: I16F16,
+    /// The relative humidity rate of change in percent per minute (%/min) above which readings
+    /// are considered changing.
+    pub humidity_rate: I16F16,
+    /// The number of consecutive stable measurements after which the sampler backs off to
+    /// low precision and the slow interval.
+    pub back_off_after: u8,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            slow_interval_ms: 60_000,
+            fast_interval_ms: 5_000,
+            temperature_rate: I16F16::from_num(0.5),
+            humidity_rate: I16F16::from_num(2),
+            back_off_after: 5,
+        }
+    }
+}
+
+/// A measurement taken by [`AdaptiveSampler`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AdaptiveSample {
+    /// The measurement.
+    pub measurement: Measurement,
+    /// The precision used for the measurement.
+    pub precision: Precision,
+    /// The time to wait until the next measurement in milliseconds.
+    pub next_interval_ms: u32,
+}
+
+/// Adapts precision and sampling rate to how fast readings are changing.
+///
+/// While readings are stable, the sampler measures with [`Precision::Low`] at
+/// [`AdaptiveConfig::slow_interval_ms`]. When the rate of change of temperature or humidity
+/// exceeds its threshold, it switches to [`Precision::High`] and
+/// [`AdaptiveConfig::fast_interval_ms`]. It backs off again after
+/// [`AdaptiveConfig::back_off_after`] consecutive stable measurements. This saves energy on
+/// battery-powered sensors when accuracy does not matter.
+#[derive(Debug)]
+pub struct AdaptiveSampler<I, D, A = Address> {
+    sensor: Sht4x<I, D, A>,
+    config: AdaptiveConfig,
+    last: Option<(Measurement, u64)>,
+    fast: bool,
+    stable_count: u8,
+}
+
+impl<I, D, A> AdaptiveSampler<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new sampler for the given driver starting with low precision.
+    pub fn new(sensor: Sht4x<I, D, A>, config: AdaptiveConfig) -> Self {
+        Self {
+            sensor,
+            config,
+            last: None,
+            fast: false,
+            stable_count: 0,
+        }
+    }
+
+    /// Destroys the sampler and returns the driver.
+    pub fn destroy(self) -> Sht4x<I, D, A> {
+        self.sensor
+    }
+
+    /// Returns the precision for the next measurement.
+    pub fn precision(&self) -> Precision {
+        if self.fast {
+            Precision::High
+        } else {
+            Precision::Low
+        }
+    }
+
+    /// Returns the time to wait between measurements in milliseconds.
+    pub fn interval_ms(&self) -> u32 {
+        if self.fast {
+            self.config.fast_interval_ms
+        } else {
+            self.config.slow_interval_ms
+        }
+    }
+
+    /// Performs a measurement at the given time in milliseconds and adapts precision and
+    /// interval for the next one.
+    ///
+    /// The time has to be monotonic, for example from a free-running timer.
+    pub fn sample(
+        &mut self,
+        now_ms: u64,
+        delay: &mut D,
+    ) -> Result<AdaptiveSample, Error<I::Error>> {
+        let precision = self.precision();
+        let measurement = self.sensor.measure(precision, delay)?;
+
+        let changing = match self.last.replace((measurement, now_ms)) {
+            Some((last, last_ms)) if now_ms > last_ms => {
+                let elapsed_ms = now_ms - last_ms;
+                exceeds(
+                    measurement.temperature_celsius() - last.temperature_celsius(),
+                    elapsed_ms,
+                    self.config.temperature_rate,
+                ) || exceeds(
+                    measurement.humidity_percent() - last.humidity_percent(),
+                    elapsed_ms,
+                    self.config.humidity_rate,
+                )
+            }
+            _ => false,
+        };
+
+        if changing {
+            self.fast = true;
+            self.stable_count = 0;
+        } else if self.fast {
+            self.stable_count = self.stable_count.saturating_add(1);
+            if self.stable_count >= self.config.back_off_after {
+                self.fast = false;
+                self.stable_count = 0;
+            }
+        }
+
+        Ok(AdaptiveSample {
+            measurement,
+            precision,
+            next_interval_ms: self.interval_ms(),
+        })
+    }
+}
+
+/// Returns whether the change over the elapsed time exceeds the given rate per minute.
+fn exceeds(change: I16F16, elapsed_ms: u64, rate_per_min: I16F16) -> bool {
+    let rate = I48F16::from_num(change.abs()) * 60_000 / I48F16::saturating_from_num(elapsed_ms);
+    rate > I48F16::from_num(rate_per_min)
+}
@@ -0,0 +1,141 @@
+//! Experimental airflow estimation using the sensor's heater as a thermal anemometer.
+//!
+//! Airflow over the sensor carries away heat and reduces the temperature rise caused by a heater
+//! pulse. Comparing the rise against the one in still air gives a relative measure of the airflow
+//! which is good enough for detecting a failed fan:
+//!
+//! ```rust ignore
+//! use sht4x::airflow::Anemometer;
+//!
+//! let mut anemometer = Anemometer::new(sensor);
+//! // With the fan turned off, for example at the end of the production line.
+//! let calibration = anemometer.calibrate(HeatingPower::Medium, HeatingDuration::Long, &mut delay)?;
+//! // Later on, with the fan running.
+//! let estimate = anemometer.estimate(&calibration, &mut delay)?;
+//! if estimate.relative_airflow < I16F16::from_num(0.5) {
+//!     // The fan has probably failed.
+//! }
+//! ```
+//!
+//! The estimate depends on the sensor's placement, its enclosure and the ambient temperature and
+//! humidity. It is not calibrated to any unit and the API might change in the future.
+//!
+//! **Note:** The heater is designed to be used up to 10 % of the sensor's lifetime. Please check
+//! the
+//! [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf),
+//! section 4.9 _Heater Operation_ for details.
+
+use crate::{
+    error::Error,
+    types::{Address, HeatingDuration, HeatingPower, Precision, SensorAddress},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use fixed::types::I16F16;
+
+/// Temperature rise of a heater pulse in still air.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StillAirCalibration {
+    /// The heating power used for calibration and estimation.
+    pub power: HeatingPower,
+    /// The heating duration used for calibration and estimation.
+    pub duration: HeatingDuration,
+    /// The temperature rise in degree Celsius (°C) in still air.
+    pub temperature_rise: I16F16,
+}
+
+/// Estimated airflow over the sensor.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AirflowEstimate {
+    /// The observed temperature rise in degree Celsius (°C).
+    pub temperature_rise: I16F16,
+    /// The airflow relative to the heat dissipation in still air.
+    ///
+    /// This is the still-air temperature rise divided by the observed one minus one. So it is
+    /// zero in still air and grows with the airflow. It saturates at [`I16F16::MAX`] if there
+    /// was no temperature rise at all.
+    pub relative_airflow: I16F16,
+}
+
+/// Estimates the airflow over an SHT4x sensor from the temperature rise of heater pulses.
+///
+/// See the [module documentation](self) for details.
+#[derive(Debug)]
+pub struct Anemometer<I, D, A = Address> {
+    sensor: crate::Sht4x<I, D, A>,
+}
+
+impl<I, D, A> Anemometer<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new anemometer from the given driver.
+    pub fn new(sensor: crate::Sht4x<I, D, A>) -> Self {
+        Self { sensor }
+    }
+
+    /// Destroys the anemometer and returns the driver.
+    pub fn destroy(self) -> crate::Sht4x<I, D, A> {
+        self.sensor
+    }
+
+    /// Determines the temperature rise of the given heater pulse in still air.
+    ///
+    /// This needs to be done once in the final installation with the airflow stopped. Longer and
+    /// more powerful pulses result in a larger temperature rise and a better resolution.
+    pub fn calibrate(
+        &mut self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+        delay: &mut D,
+    ) -> Result<StillAirCalibration, Error<I::Error>> {
+        let temperature_rise = self.temperature_rise(power, duration, delay)?;
+
+        Ok(StillAirCalibration {
+            power,
+            duration,
+            temperature_rise,
+        })
+    }
+
+    /// Estimates the current airflow by applying the calibrated heater pulse.
+    ///
+    /// Allow the sensor to cool down between estimations. A sensor still warm from the previous
+    /// heater pulse results in a smaller temperature rise and overestimates the airflow.
+    pub fn estimate(
+        &mut self,
+        calibration: &StillAirCalibration,
+        delay: &mut D,
+    ) -> Result<AirflowEstimate, Error<I::Error>> {
+        let temperature_rise =
+            self.temperature_rise(calibration.power, calibration.duration, delay)?;
+
+        let relative_airflow = if temperature_rise > I16F16::ZERO {
+            let ratio = calibration
+                .temperature_rise
+                .checked_div(temperature_rise)
+                .unwrap_or(I16F16::MAX);
+            (ratio - I16F16::ONE).max(I16F16::ZERO)
+        } else {
+            I16F16::MAX
+        };
+
+        Ok(AirflowEstimate {
+            temperature_rise,
+            relative_airflow,
+        })
+    }
+
+    fn temperature_rise(
+        &mut self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+        delay: &mut D,
+    ) -> Result<I16F16, Error<I::Error>> {
+        let baseline = self.sensor.measure(Precision::High, delay)?;
+        let heated = self.sensor.heat_and_measure(power, duration, delay)?;
+
+        Ok(heated.temperature_celsius() - baseline.temperature_celsius())
+    }
+}
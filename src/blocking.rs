@@ -0,0 +1,73 @@
+use crate::{
+    commands::Command,
+    error::Error,
+    responses::{serial_number_from_response, RESPONSE_LEN},
+    types::Timings,
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use sensirion_i2c::i2c;
+
+// Blocking bus access shared by the SHT4x and STS4x drivers. Both families use the same command
+// set and framing.
+
+pub(crate) fn write_command<I: I2c>(
+    i2c: &mut I,
+    address: u8,
+    command: Command,
+) -> Result<(), Error<I::Error>> {
+    i2c::write_command_u8(i2c, address, command.code()).map_err(Error::I2c)
+}
+
+pub(crate) fn write_command_and_delay_for_execution<I: I2c, D: DelayNs>(
+    i2c: &mut I,
+    address: u8,
+    command: Command,
+    timings: &Timings,
+    delay: &mut D,
+) -> Result<(), Error<I::Error>> {
+    write_command(i2c, address, command)?;
+    delay.delay_ms(command.duration_ms(timings));
+
+    Ok(())
+}
+
+pub(crate) fn read_response<I: I2c, const N: usize>(
+    i2c: &mut I,
+    address: u8,
+) -> Result<[u8; N], Error<I::Error>> {
+    let mut response = [0; N];
+
+    i2c::read_words_with_crc(i2c, address, &mut response)?;
+
+    Ok(response)
+}
+
+pub(crate) fn serial_number<I: I2c, D: DelayNs>(
+    i2c: &mut I,
+    address: u8,
+    timings: &Timings,
+    delay: &mut D,
+) -> Result<u32, Error<I::Error>> {
+    write_command_and_delay_for_execution(i2c, address, Command::SerialNumber, timings, delay)?;
+    let response = read_response::<_, RESPONSE_LEN>(i2c, address)?;
+
+    Ok(serial_number_from_response(response))
+}
+
+pub(crate) fn init<I: I2c, D: DelayNs>(
+    i2c: &mut I,
+    address: u8,
+    timings: &Timings,
+    delay: &mut D,
+) -> Result<u32, Error<I::Error>> {
+    delay.delay_ms(timings.power_up_ms);
+
+    write_command_and_delay_for_execution(i2c, address, Command::SoftReset, timings, delay)
+        .map_err(Error::absent_on_nack)?;
+    let serial_number = self::serial_number(i2c, address, timings, delay)?;
+    if self::serial_number(i2c, address, timings, delay)? != serial_number {
+        return Err(Error::InconsistentResponse);
+    }
+
+    Ok(serial_number)
+}
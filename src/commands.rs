@@ -1,3 +1,5 @@
+use crate::types::{HeatingDuration, HeatingPower, Timings};
+
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) enum Command {
@@ -31,23 +33,31 @@ impl Command {
         }
     }
 
-    pub(crate) fn duration_ms(&self) -> u32 {
-        // Values rounded up from the maximum durations given in the datasheet
-        // table 4, 'System timing specifications'.
+    pub(crate) fn duration_ms(&self, timings: &Timings) -> u32 {
+        match self {
+            Self::MeasureHighPrecision => timings.measure_high_precision_ms,
+            Self::MeasureMediumPrecision => timings.measure_medium_precision_ms,
+            Self::MeasureLowPrecision => timings.measure_low_precision_ms,
+            Self::SerialNumber => timings.serial_number_ms,
+            Self::SoftReset => timings.soft_reset_ms,
+            Self::MeasureHeated200mw1s => timings.heat_long_ms,
+            Self::MeasureHeated200mw0p1s => timings.heat_short_ms,
+            Self::MeasureHeated110mw1s => timings.heat_long_ms,
+            Self::MeasureHeated110mw0p1s => timings.heat_short_ms,
+            Self::MeasureHeated20mw1s => timings.heat_long_ms,
+            Self::MeasureHeated20mw0p1s => timings.heat_short_ms,
+        }
+    }
+
+    pub(crate) fn heater(&self) -> Option<(HeatingPower, HeatingDuration)> {
         match self {
-            Self::MeasureHighPrecision => 9,
-            Self::MeasureMediumPrecision => 5,
-            Self::MeasureLowPrecision => 2,
-            // There is no explicit time given for the serial number, but reading it immediately
-            // results in a NACK. So be a bit more patient here.
-            Self::SerialNumber => 1,
-            Self::SoftReset => 1,
-            Self::MeasureHeated200mw1s => 1100,
-            Self::MeasureHeated200mw0p1s => 110,
-            Self::MeasureHeated110mw1s => 1100,
-            Self::MeasureHeated110mw0p1s => 110,
-            Self::MeasureHeated20mw1s => 1100,
-            Self::MeasureHeated20mw0p1s => 110,
+            Self::MeasureHeated200mw1s => Some((HeatingPower::High, HeatingDuration::Long)),
+            Self::MeasureHeated200mw0p1s => Some((HeatingPower::High, HeatingDuration::Short)),
+            Self::MeasureHeated110mw1s => Some((HeatingPower::Medium, HeatingDuration::Long)),
+            Self::MeasureHeated110mw0p1s => Some((HeatingPower::Medium, HeatingDuration::Short)),
+            Self::MeasureHeated20mw1s => Some((HeatingPower::Low, HeatingDuration::Long)),
+            Self::MeasureHeated20mw0p1s => Some((HeatingPower::Low, HeatingDuration::Short)),
+            _ => None,
         }
     }
 }
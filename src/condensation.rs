@@ -0,0 +1,228 @@
+use crate::{
+    error::Error,
+    sht4x::Sht4x,
+    types::{Address, HeatingDuration, HeatingPower, Measurement, Precision, SensorAddress},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use fixed::types::I16F16;
+
+/// Configuration for [`CondensationRecovery`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CondensationRecoveryConfig {
+    /// The precision to use for normal measurements.
+    pub precision: Precision,
+    /// The relative humidity in percent (%) above which a recovery might be needed.
+    pub threshold: I16F16,
+    /// The time in milliseconds the relative humidity has to stay above the threshold before a
+    /// recovery starts.
+    pub trigger_after_ms: u32,
+    /// The heating power of the recovery pulses.
+    pub power: HeatingPower,
+    /// The heating duration of the recovery pulses.
+    pub duration: HeatingDuration,
+    /// The number of heater pulses per recovery.
+    pub pulses: u8,
+    /// The time in milliseconds between the end of a heater pulse and the start of the next one.
+    pub pulse_interval_ms: u32,
+    /// The time in milliseconds to let the sensor cool down after the end of the last heater
+    /// pulse.
+    pub cool_down_ms: u32,
+    /// The maximum relative humidity change in percent (%) between two measurements after cooling
+    /// down for considering the reading stabilised.
+    pub stable_band: I16F16,
+    /// The minimum time in milliseconds between the two measurements compared against
+    /// [`stable_band`](Self::stable_band).
+    pub settle_interval_ms: u32,
+}
+
+impl Default for CondensationRecoveryConfig {
+    /// Three 200 mW pulses of 1 s each with pauses of 10 s after humidity above 95 % for one
+    /// minute.
+    fn default() -> Self {
+        Self {
+            precision: Precision::High,
+            threshold: I16F16::from_num(95),
+            trigger_after_ms: 60_000,
+            power: HeatingPower::High,
+            duration: HeatingDuration::Long,
+            pulses: 3,
+            pulse_interval_ms: 10_000,
+            cool_down_ms: 30_000,
+            stable_band: I16F16::from_num(1),
+            settle_interval_ms: 10_000,
+        }
+    }
+}
+
+/// Phase of a [`CondensationRecovery`].
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RecoveryPhase {
+    /// Performing normal measurements and watching the relative humidity.
+    Monitoring,
+    /// Applying heater pulses.
+    Heating {
+        /// The number of heater pulses still to apply.
+        pulses_left: u8,
+    },
+    /// Waiting for the sensor to cool down after the last heater pulse.
+    CoolingDown,
+    /// Performing normal measurements until the reading has stabilised.
+    Settling,
+}
+
+/// Event reported by [`CondensationRecovery::step`].
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RecoveryEvent {
+    /// The relative humidity stayed above the threshold and a recovery started.
+    Started,
+    /// The reading stabilised after a recovery.
+    Stabilised,
+}
+
+/// Outcome of a single [`CondensationRecovery::step`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RecoveryStep {
+    /// The normal measurement, if one was taken. It is never affected by the heater and safe to
+    /// pass on to the application.
+    pub measurement: Option<Measurement>,
+    /// The measurement taken at the end of a heater pulse, if one was applied.
+    pub heated: Option<Measurement>,
+    /// The event which occurred during this step, if any.
+    pub event: Option<RecoveryEvent>,
+}
+
+/// Recovers a sensor from condensation and creep with heater pulses.
+///
+/// After condensation or long exposure to high humidity, the sensor's humidity reading is stuck
+/// at 100 % or creeps. Sensirion recommends heater pulses for recovering from this. The
+/// controller watches the relative humidity of normal measurements and starts a sequence of
+/// heater pulses when it stays above [`CondensationRecoveryConfig::threshold`]. After the
+/// sequence and a cool-down period, it reports [`RecoveryEvent::Stabilised`] once two measurements
+/// taken at least [`CondensationRecoveryConfig::settle_interval_ms`] apart agree within
+/// [`CondensationRecoveryConfig::stable_band`].
+///
+/// The controller is driven by calling [`step`](Self::step) periodically with a monotonic
+/// timestamp. Normal measurements are suppressed while heating and cooling down and heated ones
+/// are reported separately. So heated readings never leak into application data.
+#[derive(Debug)]
+pub struct CondensationRecovery<I, D, A = Address> {
+    sensor: Sht4x<I, D, A>,
+    config: CondensationRecoveryConfig,
+    phase: RecoveryPhase,
+    above_since_ms: Option<u64>,
+    next_ms: u64,
+    settle_reference: Option<(I16F16, u64)>,
+}
+
+impl<I, D, A> CondensationRecovery<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new controller for the given driver.
+    pub fn new(sensor: Sht4x<I, D, A>, config: CondensationRecoveryConfig) -> Self {
+        Self {
+            sensor,
+            config,
+            phase: RecoveryPhase::Monitoring,
+            above_since_ms: None,
+            next_ms: 0,
+            settle_reference: None,
+        }
+    }
+
+    /// Destroys the controller and returns the driver.
+    pub fn destroy(self) -> Sht4x<I, D, A> {
+        self.sensor
+    }
+
+    /// Returns the current phase.
+    pub fn phase(&self) -> RecoveryPhase {
+        self.phase
+    }
+
+    /// Performs the next step of monitoring or recovery at the given time in milliseconds.
+    ///
+    /// The time has to be monotonic, for example from a free-running timer. On errors, the step
+    /// gets retried on the next call.
+    pub fn step(&mut self, now_ms: u64, delay: &mut D) -> Result<RecoveryStep, Error<I::Error>> {
+        let mut step = RecoveryStep {
+            measurement: None,
+            heated: None,
+            event: None,
+        };
+
+        match self.phase {
+            RecoveryPhase::Monitoring => {
+                let measurement = self.sensor.measure(self.config.precision, delay)?;
+                step.measurement = Some(measurement);
+
+                if measurement.humidity_percent() > self.config.threshold {
+                    let since = *self.above_since_ms.get_or_insert(now_ms);
+                    if now_ms.saturating_sub(since) >= u64::from(self.config.trigger_after_ms)
+                        && self.config.pulses > 0
+                    {
+                        self.above_since_ms = None;
+                        self.next_ms = now_ms;
+                        self.phase = RecoveryPhase::Heating {
+                            pulses_left: self.config.pulses,
+                        };
+                        step.event = Some(RecoveryEvent::Started);
+                    }
+                } else {
+                    self.above_since_ms = None;
+                }
+            }
+            RecoveryPhase::Heating { pulses_left } if now_ms >= self.next_ms => {
+                let heated =
+                    self.sensor
+                        .heat_and_measure(self.config.power, self.config.duration, delay)?;
+                step.heated = Some(heated);
+
+                // The heater pulse started at `now_ms` and has just ended.
+                let heated_until_ms =
+                    now_ms + u64::from(self.sensor.timings().heating_ms(self.config.duration));
+                let pulses_left = pulses_left - 1;
+                if pulses_left > 0 {
+                    self.next_ms = heated_until_ms + u64::from(self.config.pulse_interval_ms);
+                    self.phase = RecoveryPhase::Heating { pulses_left };
+                } else {
+                    self.next_ms = heated_until_ms + u64::from(self.config.cool_down_ms);
+                    self.phase = RecoveryPhase::CoolingDown;
+                }
+            }
+            RecoveryPhase::CoolingDown if now_ms >= self.next_ms => {
+                self.settle_reference = None;
+                self.phase = RecoveryPhase::Settling;
+                return self.step(now_ms, delay);
+            }
+            RecoveryPhase::Settling => {
+                let measurement = self.sensor.measure(self.config.precision, delay)?;
+                step.measurement = Some(measurement);
+
+                // Compare against a reference measurement taken at least the settle interval
+                // before. Otherwise, calling this in quick succession would always agree.
+                let humidity = measurement.humidity_percent();
+                match self.settle_reference {
+                    Some((_, since_ms))
+                        if now_ms.saturating_sub(since_ms)
+                            < u64::from(self.config.settle_interval_ms) => {}
+                    Some((reference, _))
+                        if (humidity - reference).abs() <= self.config.stable_band =>
+                    {
+                        self.settle_reference = None;
+                        self.phase = RecoveryPhase::Monitoring;
+                        step.event = Some(RecoveryEvent::Stabilised);
+                    }
+                    _ => self.settle_reference = Some((humidity, now_ms)),
+                }
+            }
+            RecoveryPhase::Heating { .. } | RecoveryPhase::CoolingDown => {}
+        }
+
+        Ok(step)
+    }
+}
@@ -0,0 +1,70 @@
+use embedded_hal::delay::DelayNs;
+
+/// A delay which splits long delays into shorter chunks and invokes a hook in between.
+///
+/// Heater measurements like [`HeatingDuration::Long`](crate::HeatingDuration::Long) block for
+/// more than a second, which might exceed the window of a watchdog. Wrapping the delay passed to
+/// [`Sht4x`](crate::Sht4x) into a `ChunkedDelay` allows to pet the watchdog or to service other
+/// time-critical work while waiting for the sensor.
+///
+/// ```rust ignore
+/// let mut delay = ChunkedDelay::new(delay, 100, || watchdog.feed());
+/// let measurement = sht40.heat_and_measure(HeatingPower::Low, HeatingDuration::Long, &mut delay);
+/// ```
+#[derive(Debug)]
+pub struct ChunkedDelay<D, F> {
+    delay: D,
+    chunk_ms: u32,
+    hook: F,
+}
+
+impl<D, F> ChunkedDelay<D, F>
+where
+    D: DelayNs,
+    F: FnMut(),
+{
+    /// Creates a new chunked delay from the given delay, chunk length in milliseconds, and hook.
+    ///
+    /// A chunk length of zero gets treated as one millisecond.
+    pub fn new(delay: D, chunk_ms: u32, hook: F) -> Self {
+        Self {
+            delay,
+            chunk_ms: chunk_ms.max(1),
+            hook,
+        }
+    }
+
+    /// Destroys the chunked delay and returns the wrapped delay and hook.
+    pub fn destroy(self) -> (D, F) {
+        (self.delay, self.hook)
+    }
+
+    fn delay_chunked(&mut self, mut remaining: u32, chunk: u32, delay: fn(&mut D, u32)) {
+        while remaining > chunk {
+            delay(&mut self.delay, chunk);
+            remaining -= chunk;
+            (self.hook)();
+        }
+        delay(&mut self.delay, remaining);
+    }
+}
+
+impl<D, F> DelayNs for ChunkedDelay<D, F>
+where
+    D: DelayNs,
+    F: FnMut(),
+{
+    fn delay_ns(&mut self, ns: u32) {
+        let chunk = self.chunk_ms.saturating_mul(1_000_000);
+        self.delay_chunked(ns, chunk, D::delay_ns);
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        let chunk = self.chunk_ms.saturating_mul(1_000);
+        self.delay_chunked(us, chunk, D::delay_us);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay_chunked(ms, self.chunk_ms, D::delay_ms);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    error::Error,
+    sht4x_async::Sht4xAsync,
+    types::{Address, Measurement, Precision, SensorAddress},
+};
+use embassy_sync::{blocking_mutex::raw::RawMutex, pubsub, watch};
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+
+/// A reading published by [`MeasurementTask`].
+pub type Reading<E> = Result<Measurement, Error<E>>;
+
+/// Destination for readings published by [`MeasurementTask`].
+///
+/// This trait is implemented for the publishers of `embassy-sync`'s
+/// [`PubSubChannel`](embassy_sync::pubsub::PubSubChannel) and the sender of its
+/// [`Watch`](embassy_sync::watch::Watch). Publishing must not block the task, so a full
+/// `PubSubChannel` drops its oldest message.
+pub trait MeasurementSink<T> {
+    /// Publishes a single value to all subscribers.
+    fn publish(&mut self, value: T);
+}
+
+impl<M, T, const CAP: usize, const SUBS: usize, const PUBS: usize> MeasurementSink<T>
+    for pubsub::Publisher<'_, M, T, CAP, SUBS, PUBS>
+where
+    M: RawMutex,
+    T: Clone,
+{
+    fn publish(&mut self, value: T) {
+        self.publish_immediate(value);
+    }
+}
+
+impl<M, T, const CAP: usize, const SUBS: usize, const PUBS: usize> MeasurementSink<T>
+    for pubsub::ImmediatePublisher<'_, M, T, CAP, SUBS, PUBS>
+where
+    M: RawMutex,
+    T: Clone,
+{
+    fn publish(&mut self, value: T) {
+        self.publish_immediate(value);
+    }
+}
+
+impl<M, T, const N: usize> MeasurementSink<T> for watch::Sender<'_, M, T, N>
+where
+    M: RawMutex,
+    T: Clone,
+{
+    fn publish(&mut self, value: T) {
+        self.send(value);
+    }
+}
+
+/// Configuration for [`MeasurementTask`].
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MeasurementTaskConfig {
+    /// The precision to use for the periodic measurements.
+    pub precision: Precision,
+    /// The time to wait between two consecutive measurements in milliseconds.
+    pub interval_ms: u32,
+    /// The number of consecutive failed measurements after which the sensor gets re-initialized
+    /// with a soft reset. Zero disables the re-initialization.
+    pub max_consecutive_errors: u8,
+}
+
+impl Default for MeasurementTaskConfig {
+    fn default() -> Self {
+        Self {
+            precision: Precision::High,
+            interval_ms: 1000,
+            max_consecutive_errors: 3,
+        }
+    }
+}
+
+/// A ready-made task body for periodically sampling a sensor with
+/// [Embassy](https://embassy.dev).
+///
+/// The task owns the driver and its delay and publishes every [`Reading`], successful or not,
+/// to a [`MeasurementSink`]. So several consumers can subscribe to the same sensor. After
+/// [`MeasurementTaskConfig::max_consecutive_errors`] failed measurements in a row, the sensor
+/// gets re-initialized with a soft reset.
+///
+/// ```rust ignore
+/// static READINGS: PubSubChannel<CriticalSectionRawMutex, Reading<I2cError>, 4, 3, 1> =
+///     PubSubChannel::new();
+///
+/// #[embassy_executor::task]
+/// async fn sht4x_task(i2c: I2cBus, delay: Delay) -> ! {
+///     let sensor = Sht4xAsync::new(i2c);
+///     let publisher = READINGS.publisher().unwrap();
+///     MeasurementTask::new(sensor, delay, publisher, MeasurementTaskConfig::default())
+///         .run()
+///         .await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MeasurementTask<I, D, S, A = Address> {
+    sensor: Sht4xAsync<I, D, A>,
+    delay: D,
+    sink: S,
+    config: MeasurementTaskConfig,
+    consecutive_errors: u8,
+}
+
+impl<I, D, S, A> MeasurementTask<I, D, S, A>
+where
+    I: I2c,
+    I::Error: Clone,
+    D: DelayNs,
+    S: MeasurementSink<Reading<I::Error>>,
+    A: SensorAddress,
+{
+    /// Creates a new task from the given driver, delay and sink.
+    pub fn new(
+        sensor: Sht4xAsync<I, D, A>,
+        delay: D,
+        sink: S,
+        config: MeasurementTaskConfig,
+    ) -> Self {
+        Self {
+            sensor,
+            delay,
+            sink,
+            config,
+            consecutive_errors: 0,
+        }
+    }
+
+    /// Destroys the task and returns the driver, delay and sink.
+    pub fn destroy(self) -> (Sht4xAsync<I, D, A>, D, S) {
+        (self.sensor, self.delay, self.sink)
+    }
+
+    /// Performs a single measurement and publishes its outcome.
+    ///
+    /// Performs a soft reset when the configured number of consecutive errors has been reached.
+    pub async fn step(&mut self) {
+        let reading = self
+            .sensor
+            .measure(self.config.precision, &mut self.delay)
+            .await;
+
+        if reading.is_ok() {
+            self.consecutive_errors = 0;
+        } else {
+            self.consecutive_errors = self.consecutive_errors.saturating_add(1);
+        }
+
+        self.sink.publish(reading);
+
+        if self.config.max_consecutive_errors > 0
+            && self.consecutive_errors >= self.config.max_consecutive_errors
+            && self.sensor.soft_reset(&mut self.delay).await.is_ok()
+        {
+            self.consecutive_errors = 0;
+        }
+    }
+
+    /// Runs the task forever, performing a measurement step every
+    /// [`MeasurementTaskConfig::interval_ms`].
+    pub async fn run(mut self) -> ! {
+        loop {
+            self.step().await;
+            self.delay.delay_ms(self.config.interval_ms).await;
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    commands::Command,
+    types::{HeatingDuration, HeatingPower, Precision, Timings},
+};
+
+/// The supply voltage in millivolts (mV) at which the nominal heating powers are specified.
+const HEATER_VOLTAGE_MV: u64 = 3300;
+
+/// An operation of the sensor consuming energy.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Operation {
+    /// A measurement with the given precision.
+    Measure(Precision),
+    /// A measurement with the heater activated.
+    HeatAndMeasure(HeatingPower, HeatingDuration),
+    /// Reading the serial number.
+    SerialNumber,
+    /// A soft reset.
+    SoftReset,
+}
+
+impl From<Operation> for Command {
+    fn from(operation: Operation) -> Self {
+        match operation {
+            Operation::Measure(precision) => Command::from(precision),
+            Operation::HeatAndMeasure(power, duration) => Command::from((power, duration)),
+            Operation::SerialNumber => Command::SerialNumber,
+            Operation::SoftReset => Command::SoftReset,
+        }
+    }
+}
+
+/// Electrical characteristics for estimating the sensor's energy consumption.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SupplyModel {
+    /// The supply voltage in millivolts (mV).
+    pub voltage_mv: u32,
+    /// The supply current while executing a command in microampere (µA).
+    pub active_current_ua: u32,
+    /// The supply current while idle in nanoampere (nA).
+    pub idle_current_na: u32,
+}
+
+impl SupplyModel {
+    /// Typical values at 3.3 V from the datasheet's electrical specifications.
+    pub const TYPICAL: Self = Self {
+        voltage_mv: 3300,
+        active_current_ua: 320,
+        idle_current_na: 80,
+    };
+
+    /// Maximum values at 3.3 V from the datasheet's electrical specifications.
+    pub const MAXIMUM: Self = Self {
+        voltage_mv: 3300,
+        active_current_ua: 500,
+        idle_current_na: 1000,
+    };
+
+    /// Returns the charge drawn by an operation in nanocoulomb (nC, microampere times
+    /// millisecond).
+    ///
+    /// The sensor draws the active current for the command's execution time from the given
+    /// [`Timings`], the same ones the driver waits for. Heater operations additionally draw the
+    /// heater current for the nominal heating duration. The heater is a resistor specified by its
+    /// heating power at 3.3 V. So its current scales with the supply voltage and its power with
+    /// the square of it.
+    pub fn charge_nc(&self, operation: Operation, timings: &Timings) -> u64 {
+        let command = Command::from(operation);
+        let active = u64::from(self.active_current_ua) * u64::from(command.duration_ms(timings));
+        let heater = match command.heater() {
+            Some((power, duration)) => {
+                let current_ua =
+                    u64::from(power.milliwatts()) * 1_000_000 * u64::from(self.voltage_mv)
+                        / (HEATER_VOLTAGE_MV * HEATER_VOLTAGE_MV);
+                current_ua * u64::from(duration.milliseconds())
+            }
+            None => 0,
+        };
+
+        active + heater
+    }
+
+    /// Returns the energy consumed by an operation in microjoule (µJ).
+    ///
+    /// See [`charge_nc`](Self::charge_nc) for details.
+    pub fn energy_uj(&self, operation: Operation, timings: &Timings) -> u64 {
+        self.charge_nc(operation, timings) * u64::from(self.voltage_mv) / 1_000_000
+    }
+}
+
+impl Default for SupplyModel {
+    fn default() -> Self {
+        Self::TYPICAL
+    }
+}
+
+/// Accumulates the operations of a sampling schedule for estimating the average supply current.
+///
+/// ```rust ignore
+/// // A high-precision measurement every ten seconds and a heater cycle every ten minutes.
+/// let mut budget = PowerBudget::new(SupplyModel::TYPICAL, Timings::MAXIMUM);
+/// budget.add(Operation::Measure(Precision::High), 60);
+/// budget.add(Operation::HeatAndMeasure(HeatingPower::High, HeatingDuration::Long), 1);
+/// let average_na = budget.average_current_na(600_000);
+/// ```
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PowerBudget {
+    model: SupplyModel,
+    timings: Timings,
+    charge_nc: u64,
+    active_ms: u64,
+}
+
+impl PowerBudget {
+    /// Creates a new, empty budget for the given supply model and command execution times.
+    pub fn new(model: SupplyModel, timings: Timings) -> Self {
+        Self {
+            model,
+            timings,
+            charge_nc: 0,
+            active_ms: 0,
+        }
+    }
+
+    /// Adds the given number of operations.
+    pub fn add(&mut self, operation: Operation, count: u32) {
+        let command = Command::from(operation);
+        let count = u64::from(count);
+
+        self.charge_nc += self.model.charge_nc(operation, &self.timings) * count;
+        self.active_ms += u64::from(command.duration_ms(&self.timings)) * count;
+    }
+
+    /// Returns the charge drawn by the added operations in nanocoulomb (nC).
+    pub fn charge_nc(&self) -> u64 {
+        self.charge_nc
+    }
+
+    /// Returns the energy consumed by the added operations in microjoule (µJ).
+    pub fn energy_uj(&self) -> u64 {
+        self.charge_nc * u64::from(self.model.voltage_mv) / 1_000_000
+    }
+
+    /// Returns the average supply current in nanoampere (nA) when the added operations repeat
+    /// every `period_ms` milliseconds.
+    ///
+    /// The sensor is considered idle for the remainder of the period.
+    pub fn average_current_na(&self, period_ms: u64) -> u64 {
+        if period_ms == 0 {
+            return 0;
+        }
+
+        // Sum up in picocoulomb (nanoampere times millisecond).
+        let idle_ms = period_ms.saturating_sub(self.active_ms);
+        let charge_pc = self.charge_nc * 1000 + u64::from(self.model.idle_current_na) * idle_ms;
+
+        charge_pc / period_ms
+    }
+}
@@ -1,3 +1,4 @@
+use embedded_hal::i2c::ErrorKind;
 use sensirion_i2c::i2c;
 
 /// Error conditions from accessing SHT4x sensors.
@@ -9,6 +10,25 @@ pub enum Error<E> {
     I2c(E),
     /// Failed CRC verification of sensor data.
     Crc,
+    /// A bus transaction or delay did not complete in time.
+    Timeout,
+    /// The sensor did not acknowledge its address during initialization.
+    NotPresent,
+    /// The sensor responded inconsistently during initialization.
+    InconsistentResponse,
+}
+
+impl<E> Error<E>
+where
+    E: embedded_hal::i2c::Error,
+{
+    /// Reports a not acknowledged address as absent sensor.
+    pub(crate) fn absent_on_nack(self) -> Self {
+        match self {
+            Error::I2c(e) if matches!(e.kind(), ErrorKind::NoAcknowledge(_)) => Error::NotPresent,
+            other => other,
+        }
+    }
 }
 
 impl<E> From<i2c::Error<E>> for Error<E::Error>
@@ -23,3 +43,27 @@ where
         }
     }
 }
+
+/// Error conditions from measuring with redundant sensors.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum RedundancyError<E> {
+    /// No sensor could be measured at all. Contains the error of the last failing sensor.
+    Sensor(Error<E>),
+    /// The sensors did not agree on a measurement.
+    NoConsensus,
+}
+
+/// Error conditions from recovering a stuck I2C bus.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BusRecoveryError<SclE, SdaE> {
+    /// Failed to access the SCL pin.
+    Scl(SclE),
+    /// Failed to access the SDA pin.
+    Sda(SdaE),
+    /// SDA is still held low after clocking out nine pulses.
+    SdaStuckLow,
+}
@@ -0,0 +1,226 @@
+use crate::types::{Measurement, SensorData};
+use core::marker::PhantomData;
+use fixed::{
+    traits::Fixed,
+    types::{I16F16, I32F32},
+};
+
+mod sealed {
+    use fixed::types::I32F32;
+
+    pub trait Sealed: Copy {
+        fn to_channels(&self) -> [I32F32; 2];
+        fn from_channels(channels: [I32F32; 2]) -> Self;
+    }
+}
+
+/// Values which can be filtered: [`Measurement`] and raw [`SensorData`].
+///
+/// Temperature and humidity get filtered independently with a higher internal resolution than the
+/// values themselves.
+pub trait FilterSample: sealed::Sealed {}
+
+impl sealed::Sealed for Measurement {
+    fn to_channels(&self) -> [I32F32; 2] {
+        [
+            I32F32::from_num(self.temperature_celsius()),
+            I32F32::from_num(self.humidity_percent()),
+        ]
+    }
+
+    fn from_channels([temperature, humidity]: [I32F32; 2]) -> Self {
+        Measurement::new(
+            I16F16::saturating_from_num(temperature),
+            I16F16::saturating_from_num(humidity),
+        )
+    }
+}
+
+impl FilterSample for Measurement {}
+
+impl sealed::Sealed for SensorData {
+    fn to_channels(&self) -> [I32F32; 2] {
+        [
+            I32F32::from_num(self.temperature),
+            I32F32::from_num(self.humidity),
+        ]
+    }
+
+    fn from_channels([temperature, humidity]: [I32F32; 2]) -> Self {
+        SensorData {
+            temperature: temperature.round().saturating_to_num(),
+            humidity: humidity.round().saturating_to_num(),
+        }
+    }
+}
+
+impl FilterSample for SensorData {}
+
+/// Simple moving average over the last `N` values.
+///
+/// Until `N` values have been added, it averages over the values added so far.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MovingAverage<T, const N: usize> {
+    window: [[I32F32; 2]; N],
+    next: usize,
+    len: usize,
+    _sample: PhantomData<T>,
+}
+
+impl<T: FilterSample, const N: usize> MovingAverage<T, N> {
+    const VALID: () = assert!(N > 0, "the window needs to hold at least one value");
+
+    /// Creates a new, empty filter.
+    pub const fn new() -> Self {
+        let () = Self::VALID;
+        Self {
+            window: [[I32F32::ZERO; 2]; N],
+            next: 0,
+            len: 0,
+            _sample: PhantomData,
+        }
+    }
+
+    /// Discards all values added so far.
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// Adds a value and returns the current average.
+    pub fn update(&mut self, value: T) -> T {
+        self.window[self.next] = value.to_channels();
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        let mut sum = [I32F32::ZERO; 2];
+        for channels in &self.window[..self.len] {
+            sum[0] += channels[0];
+            sum[1] += channels[1];
+        }
+        let len = self.len as i64;
+
+        T::from_channels([sum[0] / len, sum[1] / len])
+    }
+}
+
+impl<T: FilterSample, const N: usize> Default for MovingAverage<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Exponential moving average.
+///
+/// Every new value gets weighted with `alpha` and the previous average with `1 - alpha`. So a
+/// smaller alpha results in more smoothing. The first value initializes the average.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ExponentialMovingAverage<T> {
+    alpha: I32F32,
+    average: Option<[I32F32; 2]>,
+    _sample: PhantomData<T>,
+}
+
+impl<T: FilterSample> ExponentialMovingAverage<T> {
+    /// Creates a new filter with the given weight for new values. It gets clamped to the range
+    /// from zero to one.
+    pub fn new(alpha: I16F16) -> Self {
+        Self {
+            alpha: I32F32::from_num(alpha.clamp(I16F16::ZERO, I16F16::ONE)),
+            average: None,
+            _sample: PhantomData,
+        }
+    }
+
+    /// Discards the current average.
+    pub fn reset(&mut self) {
+        self.average = None;
+    }
+
+    /// Adds a value and returns the current average.
+    pub fn update(&mut self, value: T) -> T {
+        let channels = value.to_channels();
+        let average = match self.average {
+            Some(average) => [
+                average[0] + self.alpha * (channels[0] - average[0]),
+                average[1] + self.alpha * (channels[1] - average[1]),
+            ],
+            None => channels,
+        };
+        self.average = Some(average);
+
+        T::from_channels(average)
+    }
+}
+
+/// Median of the last `N` values for rejecting spikes.
+///
+/// Temperature and humidity medians get determined independently. Until `N` values have been
+/// added, it uses the values added so far. For an even number of values, it returns the mean of
+/// the two middle ones.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MedianFilter<T, const N: usize> {
+    window: [[I32F32; 2]; N],
+    next: usize,
+    len: usize,
+    _sample: PhantomData<T>,
+}
+
+impl<T: FilterSample, const N: usize> MedianFilter<T, N> {
+    const VALID: () = assert!(N > 0, "the window needs to hold at least one value");
+
+    /// Creates a new, empty filter.
+    pub const fn new() -> Self {
+        let () = Self::VALID;
+        Self {
+            window: [[I32F32::ZERO; 2]; N],
+            next: 0,
+            len: 0,
+            _sample: PhantomData,
+        }
+    }
+
+    /// Discards all values added so far.
+    pub fn reset(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// Adds a value and returns the current median.
+    pub fn update(&mut self, value: T) -> T {
+        self.window[self.next] = value.to_channels();
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        let mut temperatures = [I32F32::ZERO; N];
+        let mut humidities = [I32F32::ZERO; N];
+        for (index, channels) in self.window[..self.len].iter().enumerate() {
+            temperatures[index] = channels[0];
+            humidities[index] = channels[1];
+        }
+
+        T::from_channels([
+            median(&mut temperatures[..self.len]),
+            median(&mut humidities[..self.len]),
+        ])
+    }
+}
+
+impl<T: FilterSample, const N: usize> Default for MedianFilter<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sorts the values and returns their median. For an even number of values, it returns the mean of
+/// the two middle ones.
+pub(crate) fn median<F: Fixed>(values: &mut [F]) -> F {
+    values.sort_unstable();
+
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        values[middle - 1].mean(values[middle])
+    } else {
+        values[middle]
+    }
+}
@@ -0,0 +1,212 @@
+use crate::types::{HeatingDuration, HeatingPower};
+use fixed::types::{I16F16, U48F16};
+
+#[cfg(feature = "embedded-storage")]
+use embedded_storage::nor_flash::NorFlash;
+
+const MAGIC: [u8; 4] = *b"SHTH";
+const POWER_LEVELS: [HeatingPower; 3] =
+    [HeatingPower::Low, HeatingPower::Medium, HeatingPower::High];
+
+/// The length of a serialized [`HeaterUsage`] in bytes.
+pub const HEATER_USAGE_LEN: usize = 40;
+
+/// Accumulated usage of the sensor's heater.
+///
+/// The heater is designed to be used up to 10 % of the sensor's lifetime. Please check the
+/// [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf),
+/// section 4.9 _Heater Operation_ for details. This accumulator counts the heater's on-time per
+/// [`HeatingPower`] for keeping track of this budget. It does not observe the driver by itself,
+/// so every heater activation has to be [`record`](Self::record)ed.
+///
+/// The usage can be persisted across restarts with [`to_bytes`](Self::to_bytes) and
+/// [`from_bytes`](Self::from_bytes) or, with the `embedded-storage` feature, directly in NOR
+/// flash.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct HeaterUsage {
+    on_time_ms: [u64; 3],
+}
+
+impl HeaterUsage {
+    /// Creates a new accumulator without any usage.
+    pub const fn new() -> Self {
+        Self { on_time_ms: [0; 3] }
+    }
+
+    /// Records a single heater activation.
+    pub fn record(&mut self, power: HeatingPower, duration: HeatingDuration) {
+        let total = &mut self.on_time_ms[index(power)];
+        *total = total.saturating_add(u64::from(duration.milliseconds()));
+    }
+
+    /// Returns the heater's on-time at the given power in milliseconds.
+    pub fn on_time_ms(&self, power: HeatingPower) -> u64 {
+        self.on_time_ms[index(power)]
+    }
+
+    /// Returns the heater's total on-time in milliseconds.
+    pub fn total_on_time_ms(&self) -> u64 {
+        self.on_time_ms
+            .iter()
+            .fold(0, |total, on_time_ms| total.saturating_add(*on_time_ms))
+    }
+
+    /// Returns the energy consumed by the heater at the given power in millijoule (mJ).
+    pub fn energy_mj(&self, power: HeatingPower) -> u64 {
+        self.on_time_ms(power)
+            .saturating_mul(u64::from(power.milliwatts()))
+            / 1000
+    }
+
+    /// Returns the total energy consumed by the heater in millijoule (mJ).
+    pub fn total_energy_mj(&self) -> u64 {
+        POWER_LEVELS.iter().fold(0, |total, power| {
+            total.saturating_add(self.energy_mj(*power))
+        })
+    }
+
+    /// Returns the fraction of the heater budget consumed for a sensor with the given lifetime in
+    /// milliseconds.
+    ///
+    /// The budget is 10 % of the lifetime. So a return value of one means that the budget has been
+    /// used up. It saturates at [`I16F16::MAX`].
+    pub fn budget_consumed(&self, lifetime_ms: u64) -> I16F16 {
+        let budget_ms = lifetime_ms / 10;
+        if budget_ms == 0 {
+            return I16F16::MAX;
+        }
+
+        let fraction = U48F16::saturating_from_num(self.total_on_time_ms()) / budget_ms;
+        I16F16::saturating_from_num(fraction)
+    }
+
+    /// Serializes the usage for persisting it.
+    ///
+    /// The data contains a magic number and a CRC-32 for detecting uninitialized or corrupted
+    /// storage.
+    pub fn to_bytes(&self) -> [u8; HEATER_USAGE_LEN] {
+        self.encode(0)
+    }
+
+    /// Restores the usage from data serialized with [`to_bytes`](Self::to_bytes).
+    ///
+    /// Returns `None` if the data has not been serialized by `to_bytes` or got corrupted.
+    pub fn from_bytes(bytes: &[u8; HEATER_USAGE_LEN]) -> Option<Self> {
+        Self::decode(bytes).map(|(usage, _)| usage)
+    }
+
+    /// Stores the usage in NOR flash at the given offset.
+    ///
+    /// The usage is stored alternately in two sectors starting at the offset, each erased before
+    /// writing, and tagged with a sequence number. So a power loss while storing leaves the
+    /// previously stored usage intact. The offset has to be aligned to the flash's erase size and
+    /// the flash's write size has to divide [`HEATER_USAGE_LEN`].
+    #[cfg(feature = "embedded-storage")]
+    pub fn store<F: NorFlash>(&self, flash: &mut F, offset: u32) -> Result<(), F::Error> {
+        let (slot, sequence) = match Self::load_newest(flash, offset)? {
+            Some((_, slot, sequence)) => (1 - slot, sequence.wrapping_add(1)),
+            None => (0, 0),
+        };
+        let offset = offset + slot * F::ERASE_SIZE as u32;
+
+        flash.erase(offset, offset + F::ERASE_SIZE as u32)?;
+        flash.write(offset, &self.encode(sequence))
+    }
+
+    /// Loads the usage most recently stored in NOR flash at the given offset.
+    ///
+    /// Returns `Ok(None)` if there is no valid usage stored, for example on first start.
+    #[cfg(feature = "embedded-storage")]
+    pub fn load<F: NorFlash>(flash: &mut F, offset: u32) -> Result<Option<Self>, F::Error> {
+        Ok(Self::load_newest(flash, offset)?.map(|(usage, _, _)| usage))
+    }
+
+    /// Loads the valid usage with the newest sequence number along with its slot.
+    #[cfg(feature = "embedded-storage")]
+    fn load_newest<F: NorFlash>(
+        flash: &mut F,
+        offset: u32,
+    ) -> Result<Option<(Self, u32, u32)>, F::Error> {
+        let mut newest: Option<(Self, u32, u32)> = None;
+
+        for slot in 0..2 {
+            let mut bytes = [0; HEATER_USAGE_LEN];
+            flash.read(offset + slot * F::ERASE_SIZE as u32, &mut bytes)?;
+
+            if let Some((usage, sequence)) = Self::decode(&bytes) {
+                // Compare sequence numbers with wrap-around.
+                let newer =
+                    newest.is_none_or(|(_, _, newest)| (sequence.wrapping_sub(newest) as i32) > 0);
+                if newer {
+                    newest = Some((usage, slot, sequence));
+                }
+            }
+        }
+
+        Ok(newest)
+    }
+
+    /// Serializes the usage with the given sequence number.
+    ///
+    /// The layout is the magic number, the sequence number, the on-times per power and the
+    /// CRC-32 of the preceding bytes, all little endian, padded with 0xff.
+    fn encode(&self, sequence: u32) -> [u8; HEATER_USAGE_LEN] {
+        let mut bytes = [0xff; HEATER_USAGE_LEN];
+
+        bytes[..4].copy_from_slice(&MAGIC);
+        bytes[4..8].copy_from_slice(&sequence.to_le_bytes());
+        for (chunk, on_time_ms) in bytes[8..32].chunks_mut(8).zip(self.on_time_ms.iter()) {
+            chunk.copy_from_slice(&on_time_ms.to_le_bytes());
+        }
+        let crc = crc32(&bytes[..32]);
+        bytes[32..36].copy_from_slice(&crc.to_le_bytes());
+
+        bytes
+    }
+
+    /// Restores the usage and its sequence number from data serialized with
+    /// [`encode`](Self::encode).
+    fn decode(bytes: &[u8; HEATER_USAGE_LEN]) -> Option<(Self, u32)> {
+        if bytes[..4] != MAGIC || bytes[32..36] != crc32(&bytes[..32]).to_le_bytes() {
+            return None;
+        }
+
+        let mut le_bytes = [0; 4];
+        le_bytes.copy_from_slice(&bytes[4..8]);
+        let sequence = u32::from_le_bytes(le_bytes);
+
+        let mut on_time_ms = [0; 3];
+        for (on_time_ms, chunk) in on_time_ms.iter_mut().zip(bytes[8..32].chunks(8)) {
+            let mut le_bytes = [0; 8];
+            le_bytes.copy_from_slice(chunk);
+            *on_time_ms = u64::from_le_bytes(le_bytes);
+        }
+
+        Some((Self { on_time_ms }, sequence))
+    }
+}
+
+fn index(power: HeatingPower) -> usize {
+    match power {
+        HeatingPower::Low => 0,
+        HeatingPower::Medium => 1,
+        HeatingPower::High => 2,
+    }
+}
+
+/// Calculates the CRC-32 (IEEE 802.3) of the given data.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
@@ -0,0 +1,128 @@
+use crate::{
+    error::Error,
+    sht4x::Sht4x,
+    types::{Address, Measurement, Precision, SensorAddress},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Event reporting that a different sensor than before answered at the same address.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SensorReplaced {
+    /// The serial number of the previous sensor.
+    pub old: u32,
+    /// The serial number of the new sensor.
+    pub new: u32,
+}
+
+/// Detects the replacement of a sensor on a pluggable probe.
+///
+/// It remembers the serial number read by [`HotSwapMonitor::init`] and re-reads it every
+/// `check_interval` measurements as well as after a failed measurement. A changed serial number
+/// gets reported as [`SensorReplaced`] so that calibration offsets and history belonging to the
+/// previous probe can be reset.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug)]
+pub struct HotSwapMonitor<I, D, A = Address> {
+    sensor: Sht4x<I, D, A>,
+    serial_number: Option<u32>,
+    reported_serial_number: Option<u32>,
+    check_interval: u32,
+    measurements_since_check: u32,
+    check_pending: bool,
+}
+
+impl<I, D, A> HotSwapMonitor<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new monitor for the given driver which re-reads the serial number every
+    /// `check_interval` measurements. An interval of zero only re-reads it after failed
+    /// measurements and on explicit [`check`](Self::check)s.
+    pub fn new(sensor: Sht4x<I, D, A>, check_interval: u32) -> Self {
+        Self {
+            sensor,
+            serial_number: None,
+            reported_serial_number: None,
+            check_interval,
+            measurements_since_check: 0,
+            check_pending: true,
+        }
+    }
+
+    /// Destroys the monitor and returns the driver.
+    pub fn destroy(self) -> Sht4x<I, D, A> {
+        self.sensor
+    }
+
+    /// Returns the serial number of the currently known sensor.
+    pub fn serial_number(&self) -> Option<u32> {
+        self.serial_number
+    }
+
+    /// Initializes the sensor as [`Sht4x::init`] and remembers its serial number.
+    pub fn init(&mut self, delay: &mut D) -> Result<u32, Error<I::Error>> {
+        let serial_number = self.sensor.init(delay)?;
+
+        self.serial_number = Some(serial_number);
+        self.reported_serial_number = Some(serial_number);
+        self.measurements_since_check = 0;
+        self.check_pending = false;
+
+        Ok(serial_number)
+    }
+
+    /// Re-reads the serial number and reports if the sensor got replaced.
+    pub fn check(&mut self, delay: &mut D) -> Result<Option<SensorReplaced>, Error<I::Error>> {
+        self.read_serial_number(delay)?;
+
+        Ok(self.take_replacement())
+    }
+
+    /// Performs a measurement returning measurands in SI units.
+    ///
+    /// Re-reads the serial number beforehand when due and reports if the sensor got replaced. A
+    /// replacement detected before a failed measurement is reported with the next successful one.
+    pub fn measure(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+    ) -> Result<(Measurement, Option<SensorReplaced>), Error<I::Error>> {
+        let due = self.check_interval != 0 && self.measurements_since_check >= self.check_interval;
+        if self.check_pending || due {
+            self.read_serial_number(delay)?;
+        }
+
+        let measurement = self.sensor.measure(precision, delay).inspect_err(|_| {
+            self.check_pending = true;
+        })?;
+        self.measurements_since_check = self.measurements_since_check.saturating_add(1);
+
+        Ok((measurement, self.take_replacement()))
+    }
+
+    fn read_serial_number(&mut self, delay: &mut D) -> Result<(), Error<I::Error>> {
+        let serial_number = self.sensor.serial_number(delay).inspect_err(|_| {
+            self.check_pending = true;
+        })?;
+
+        self.serial_number = Some(serial_number);
+        self.measurements_since_check = 0;
+        self.check_pending = false;
+
+        Ok(())
+    }
+
+    /// Returns the replacement not yet reported to the caller, if any.
+    fn take_replacement(&mut self) -> Option<SensorReplaced> {
+        let replaced = match (self.reported_serial_number, self.serial_number) {
+            (Some(old), Some(new)) if old != new => Some(SensorReplaced { old, new }),
+            _ => None,
+        };
+        self.reported_serial_number = self.serial_number;
+
+        replaced
+    }
+}
@@ -0,0 +1,152 @@
+use crate::types::{Measurement, Precision};
+use fixed::types::{I16F16, I32F32};
+
+/// Configuration for [`KalmanFilter`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KalmanConfig {
+    /// The expected standard deviation of the actual temperature change between two updates in
+    /// degree Celsius (°C).
+    pub temperature_process_noise: I16F16,
+    /// The expected standard deviation of the actual relative humidity change between two updates
+    /// in percent (%).
+    pub humidity_process_noise: I16F16,
+}
+
+impl Default for KalmanConfig {
+    fn default() -> Self {
+        Self {
+            temperature_process_noise: I16F16::from_num(0.01),
+            humidity_process_noise: I16F16::from_num(0.05),
+        }
+    }
+}
+
+/// A smoothed measurement from [`KalmanFilter`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KalmanEstimate {
+    /// The estimated measurement.
+    pub measurement: Measurement,
+    /// The standard deviation of the temperature estimate in degree Celsius (°C).
+    pub temperature_uncertainty: I16F16,
+    /// The standard deviation of the relative humidity estimate in percent (%).
+    pub humidity_uncertainty: I16F16,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Channel {
+    estimate: I32F32,
+    variance: I32F32,
+}
+
+impl Channel {
+    fn update(&mut self, value: I32F32, process_variance: I32F32, noise_variance: I32F32) {
+        let variance = self.variance + process_variance;
+        let gain = variance / (variance + noise_variance);
+
+        self.estimate += gain * (value - self.estimate);
+        self.variance = (I32F32::ONE - gain) * variance;
+    }
+
+    fn uncertainty(&self) -> I16F16 {
+        I16F16::saturating_from_num(self.variance.sqrt())
+    }
+}
+
+/// Kalman filter tracking temperature and relative humidity.
+///
+/// The filter models temperature and humidity as independent random walks. It uses the
+/// repeatability given in the
+/// [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf)
+/// as three times the standard deviation of the measurement noise for the [`Precision`] of each
+/// measurement. So mixing measurements of different precision weights them accordingly.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KalmanFilter {
+    config: KalmanConfig,
+    channels: Option<[Channel; 2]>,
+}
+
+impl KalmanFilter {
+    /// Creates a new filter without any measurement.
+    pub fn new(config: KalmanConfig) -> Self {
+        Self {
+            config,
+            channels: None,
+        }
+    }
+
+    /// Discards the current estimate.
+    pub fn reset(&mut self) {
+        self.channels = None;
+    }
+
+    /// Returns the current estimate, if there is one.
+    pub fn estimate(&self) -> Option<KalmanEstimate> {
+        self.channels.map(estimate)
+    }
+
+    /// Incorporates a measurement taken with the given precision and returns the new estimate.
+    pub fn update(&mut self, measurement: Measurement, precision: Precision) -> KalmanEstimate {
+        let temperature = I32F32::from_num(measurement.temperature_celsius());
+        let humidity = I32F32::from_num(measurement.humidity_percent());
+        let (temperature_noise, humidity_noise) = noise_variances(precision);
+
+        let channels = match self.channels {
+            Some([mut temperature_channel, mut humidity_channel]) => {
+                temperature_channel.update(
+                    temperature,
+                    square(self.config.temperature_process_noise),
+                    temperature_noise,
+                );
+                humidity_channel.update(
+                    humidity,
+                    square(self.config.humidity_process_noise),
+                    humidity_noise,
+                );
+                [temperature_channel, humidity_channel]
+            }
+            None => [
+                Channel {
+                    estimate: temperature,
+                    variance: temperature_noise,
+                },
+                Channel {
+                    estimate: humidity,
+                    variance: humidity_noise,
+                },
+            ],
+        };
+        self.channels = Some(channels);
+
+        estimate(channels)
+    }
+}
+
+fn estimate([temperature, humidity]: [Channel; 2]) -> KalmanEstimate {
+    KalmanEstimate {
+        measurement: Measurement::new(
+            I16F16::saturating_from_num(temperature.estimate),
+            I16F16::saturating_from_num(humidity.estimate),
+        ),
+        temperature_uncertainty: temperature.uncertainty(),
+        humidity_uncertainty: humidity.uncertainty(),
+    }
+}
+
+fn square(value: I16F16) -> I32F32 {
+    let value = I32F32::from_num(value);
+    value * value
+}
+
+/// Returns the measurement noise variances for temperature and humidity.
+fn noise_variances(precision: Precision) -> (I32F32, I32F32) {
+    // Repeatability in milli degree Celsius and milli percent.
+    let (temperature, humidity) = match precision {
+        Precision::High => (40, 80),
+        Precision::Medium => (70, 150),
+        Precision::Low => (100, 250),
+    };
+    let sigma = |repeatability: i32| I32F32::from_num(repeatability) / 3000;
+    let variance = |repeatability: i32| sigma(repeatability) * sigma(repeatability);
+
+    (variance(temperature), variance(humidity))
+}
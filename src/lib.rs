@@ -3,17 +3,60 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod accuracy;
+mod adaptive;
+mod blocking;
 mod commands;
+mod condensation;
+mod delay;
+mod energy;
 mod error;
+mod filters;
+mod heater_usage;
+mod hotswap;
+mod kalman;
+mod multi;
+mod mux;
+mod recovery;
+mod redundancy;
 mod responses;
+mod scheduler;
+mod self_test;
 mod sht4x;
+mod sht4x_with_delay;
+mod sts4x;
 mod types;
 
+pub mod airflow;
+pub mod typestate;
+
+#[cfg(feature = "embassy")]
+mod embassy_task;
+#[cfg(feature = "embassy")]
+pub use self::embassy_task::{MeasurementSink, MeasurementTask, MeasurementTaskConfig, Reading};
+
 #[cfg(feature = "embedded-hal-async")]
 mod sht4x_async;
 #[cfg(feature = "embedded-hal-async")]
-pub use self::sht4x_async::Sht4xAsync;
+pub use self::sht4x_async::{Sht4xAsync, Timeout};
 
+pub use crate::accuracy::*;
+pub use crate::adaptive::*;
+pub use crate::condensation::*;
+pub use crate::delay::*;
+pub use crate::energy::*;
 pub use crate::error::*;
+pub use crate::filters::*;
+pub use crate::heater_usage::*;
+pub use crate::hotswap::*;
+pub use crate::kalman::*;
+pub use crate::multi::*;
+pub use crate::mux::*;
+pub use crate::recovery::*;
+pub use crate::redundancy::*;
+pub use crate::scheduler::*;
+pub use crate::self_test::*;
 pub use crate::sht4x::*;
+pub use crate::sht4x_with_delay::*;
+pub use crate::sts4x::*;
 pub use crate::types::*;
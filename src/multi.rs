@@ -0,0 +1,107 @@
+use crate::{
+    commands::Command,
+    error::Error,
+    responses::{sensor_data_from_response, serial_number_from_response, RESPONSE_LEN},
+    sht4x::Sht4x,
+    types::{Address, Measurement, Precision, SensorAddress, SensorData},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Coordinates measurements of multiple sensors, for example at the addresses 0x44, 0x45, and
+/// 0x46 on the same bus.
+///
+/// Instead of measuring one sensor after another, it issues the measurement command to every
+/// sensor first, waits once for the longest execution time, and then reads all responses. So
+/// sampling all sensors takes about as long as sampling a single one.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct Sht4xGroup<I, D, const N: usize, A = Address> {
+    sensors: [Sht4x<I, D, A>; N],
+}
+
+impl<I, D, const N: usize, A> Sht4xGroup<I, D, N, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new group from the given driver instances.
+    pub fn new(sensors: [Sht4x<I, D, A>; N]) -> Self {
+        Self { sensors }
+    }
+
+    /// Destroys the group and returns the driver instances.
+    pub fn destroy(self) -> [Sht4x<I, D, A>; N] {
+        self.sensors
+    }
+
+    /// Returns the driver instances of this group.
+    pub fn sensors_mut(&mut self) -> &mut [Sht4x<I, D, A>; N] {
+        &mut self.sensors
+    }
+
+    /// Reads the serial numbers of all sensors.
+    ///
+    /// The results are in the same order as the sensors passed to [`Sht4xGroup::new`].
+    pub fn serial_numbers(&mut self, delay: &mut D) -> [Result<u32, Error<I::Error>>; N] {
+        self.execute(Command::SerialNumber, delay)
+            .map(|result| result.map(serial_number_from_response))
+    }
+
+    /// Performs a measurement with every sensor returning measurands in SI units.
+    ///
+    /// The results are in the same order as the sensors passed to [`Sht4xGroup::new`]. A failing
+    /// sensor does not affect the measurements of the others.
+    pub fn measure(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+    ) -> [Result<Measurement, Error<I::Error>>; N] {
+        self.measure_raw(precision, delay)
+            .map(|result| result.map(Measurement::from))
+    }
+
+    /// Performs a measurement with every sensor returning raw sensor data.
+    ///
+    /// The results are in the same order as the sensors passed to [`Sht4xGroup::new`]. A failing
+    /// sensor does not affect the measurements of the others.
+    pub fn measure_raw(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+    ) -> [Result<SensorData, Error<I::Error>>; N] {
+        self.execute(Command::from(precision), delay)
+            .map(|result| result.map(sensor_data_from_response))
+    }
+
+    fn execute(
+        &mut self,
+        command: Command,
+        delay: &mut D,
+    ) -> [Result<[u8; RESPONSE_LEN], Error<I::Error>>; N] {
+        let started = self
+            .sensors
+            .each_mut()
+            .map(|sensor| sensor.write_command(command));
+
+        // Sensors might be configured with different timings. So wait for the slowest one.
+        let duration_ms = self
+            .sensors
+            .iter()
+            .zip(started.iter())
+            .filter(|(_, started)| started.is_ok())
+            .map(|(sensor, _)| command.duration_ms(sensor.timings()))
+            .max();
+        if let Some(duration_ms) = duration_ms {
+            delay.delay_ms(duration_ms);
+        }
+
+        let mut index = 0;
+        started.map(|started| {
+            let sensor = &mut self.sensors[index];
+            index += 1;
+
+            started.and_then(|()| sensor.read_response())
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use core::cell::RefCell;
+use embedded_hal::i2c::{ErrorType, I2c, Operation};
+
+/// Number of channels of a TCA9548A or PCA9548A multiplexer.
+pub const MUX_CHANNELS: u8 = 8;
+
+/// One or more I2C multiplexers like the TCA9548A or PCA9548A on the same bus.
+///
+/// This allows to operate multiple sensors with the same address, each on its own channel of the
+/// multiplexer. Every channel is presented as an independent I2C bus by [`I2cMux::channel`] which
+/// selects the channel before each transaction if needed. So a [`Sht4x`](crate::Sht4x) can be
+/// created for each channel.
+///
+/// ```rust ignore
+/// let mux = I2cMux::new(i2c, 0x70);
+/// let mut first = Sht4x::new(mux.channel(0).unwrap());
+/// let mut second = Sht4x::new(mux.channel(1).unwrap());
+///
+/// let first_measurement = first.measure(Precision::High, &mut delay);
+/// let second_measurement = second.measure(Precision::High, &mut delay);
+/// ```
+///
+/// Multiple multiplexers on the same bus have to be managed by a single instance created with
+/// [`I2cMux::new_multiple`]. It disconnects the channels of the other multiplexers before
+/// selecting a channel. Otherwise sensors with the same address behind different multiplexers
+/// would answer at the same time. The channels are numbered consecutively with
+/// [`MUX_CHANNELS`] channels per multiplexer in the order of the given addresses.
+///
+/// The multiplexer is shared between the channels by a [`RefCell`]. So the channels can't be
+/// sent to other threads or interrupt handlers.
+#[derive(Debug)]
+pub struct I2cMux<I, const N: usize = 1> {
+    addresses: [u8; N],
+    state: RefCell<MuxState<I>>,
+}
+
+#[derive(Debug)]
+struct MuxState<I> {
+    i2c: I,
+    selected: Option<u8>,
+}
+
+impl<I: I2c> I2cMux<I> {
+    /// Creates a new multiplexer using the given I2C bus and the multiplexer's address.
+    pub fn new(i2c: I, address: u8) -> Self {
+        Self::new_multiple(i2c, [address])
+    }
+}
+
+impl<I: I2c, const N: usize> I2cMux<I, N> {
+    /// Creates a new set of multiplexers using the given I2C bus and the multiplexers' addresses.
+    pub fn new_multiple(i2c: I, addresses: [u8; N]) -> Self {
+        Self {
+            addresses,
+            state: RefCell::new(MuxState {
+                i2c,
+                selected: None,
+            }),
+        }
+    }
+
+    /// Destroys the multiplexer and returns the used I2C bus.
+    pub fn destroy(self) -> I {
+        self.state.into_inner().i2c
+    }
+
+    /// Returns the I2C bus for the given channel or `None` if the channel does not exist.
+    pub fn channel(&self, channel: u8) -> Option<MuxChannel<'_, I, N>> {
+        if usize::from(channel) < N * usize::from(MUX_CHANNELS) {
+            Some(MuxChannel { mux: self, channel })
+        } else {
+            None
+        }
+    }
+
+    /// Disconnects all channels from the upstream bus.
+    pub fn deselect(&self) -> Result<(), I::Error> {
+        let mut state = self.state.borrow_mut();
+
+        // Forget the selection in advance. A failed write leaves it undetermined.
+        state.selected = None;
+        for address in self.addresses {
+            state.i2c.write(address, &[0])?;
+        }
+
+        Ok(())
+    }
+
+    fn with_channel<R>(
+        &self,
+        channel: u8,
+        f: impl FnOnce(&mut I) -> Result<R, I::Error>,
+    ) -> Result<R, I::Error> {
+        let mut state = self.state.borrow_mut();
+
+        if state.selected != Some(channel) {
+            let mux = usize::from(channel / MUX_CHANNELS);
+            let previous = state
+                .selected
+                .map(|selected| usize::from(selected / MUX_CHANNELS));
+
+            // Forget the selection in advance. A failed write leaves it undetermined.
+            state.selected = None;
+            for (index, address) in self.addresses.into_iter().enumerate() {
+                // Without a known selection, any of the other multiplexers might have a channel
+                // selected.
+                if index != mux && previous.is_none_or(|previous| previous == index) {
+                    state.i2c.write(address, &[0])?;
+                }
+            }
+            state
+                .i2c
+                .write(self.addresses[mux], &[1 << (channel % MUX_CHANNELS)])?;
+            state.selected = Some(channel);
+        }
+
+        f(&mut state.i2c)
+    }
+}
+
+/// A single channel of an [`I2cMux`].
+#[derive(Debug)]
+pub struct MuxChannel<'a, I, const N: usize = 1> {
+    mux: &'a I2cMux<I, N>,
+    channel: u8,
+}
+
+impl<I: I2c, const N: usize> ErrorType for MuxChannel<'_, I, N> {
+    type Error = I::Error;
+}
+
+impl<I: I2c, const N: usize> I2c for MuxChannel<'_, I, N> {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |i2c| i2c.read(address, read))
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |i2c| i2c.write(address, write))
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |i2c| i2c.write_read(address, write, read))
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.mux
+            .with_channel(self.channel, |i2c| i2c.transaction(address, operations))
+    }
+}
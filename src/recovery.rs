@@ -0,0 +1,62 @@
+use crate::error::BusRecoveryError;
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+};
+
+/// Half of a clock period at the I2C standard mode's 100 kHz.
+const HALF_PERIOD_US: u32 = 5;
+
+/// Number of clock pulses after which any device should have released SDA.
+const MAX_CLOCK_PULSES: usize = 9;
+
+/// Recovers an I2C bus where a device holds SDA low, for example after being reset in the middle
+/// of a read.
+///
+/// A stuck SDA line blocks all I2C communication, including [`Sht4x::soft_reset`]. This routine
+/// toggles SCL up to nine times until the device releases SDA and then issues a STOP condition.
+///
+/// The pins need to be configured as open-drain outputs with pull-ups, which is what the I2C
+/// peripheral uses as well. So release the pins from the I2C peripheral beforehand. Afterwards,
+/// re-create the I2C peripheral and the driver.
+///
+/// [`Sht4x::soft_reset`]: crate::Sht4x::soft_reset
+pub fn recover_bus<SCL, SDA, D>(
+    scl: &mut SCL,
+    sda: &mut SDA,
+    delay: &mut D,
+) -> Result<(), BusRecoveryError<SCL::Error, SDA::Error>>
+where
+    SCL: OutputPin,
+    SDA: InputPin + OutputPin,
+    D: DelayNs,
+{
+    sda.set_high().map_err(BusRecoveryError::Sda)?;
+    scl.set_high().map_err(BusRecoveryError::Scl)?;
+    delay.delay_us(HALF_PERIOD_US);
+
+    let mut pulses = 0;
+    while sda.is_low().map_err(BusRecoveryError::Sda)? {
+        if pulses == MAX_CLOCK_PULSES {
+            return Err(BusRecoveryError::SdaStuckLow);
+        }
+
+        scl.set_low().map_err(BusRecoveryError::Scl)?;
+        delay.delay_us(HALF_PERIOD_US);
+        scl.set_high().map_err(BusRecoveryError::Scl)?;
+        delay.delay_us(HALF_PERIOD_US);
+        pulses += 1;
+    }
+
+    // Generate a STOP condition: a rising edge on SDA while SCL is high.
+    scl.set_low().map_err(BusRecoveryError::Scl)?;
+    delay.delay_us(HALF_PERIOD_US);
+    sda.set_low().map_err(BusRecoveryError::Sda)?;
+    delay.delay_us(HALF_PERIOD_US);
+    scl.set_high().map_err(BusRecoveryError::Scl)?;
+    delay.delay_us(HALF_PERIOD_US);
+    sda.set_high().map_err(BusRecoveryError::Sda)?;
+    delay.delay_us(HALF_PERIOD_US);
+
+    Ok(())
+}
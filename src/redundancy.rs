@@ -0,0 +1,192 @@
+use crate::{
+    error::{Error, RedundancyError},
+    filters::median,
+    multi::Sht4xGroup,
+    types::{Address, Measurement, Precision, SensorAddress},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use fixed::types::{I16F16, I48F16};
+
+/// How to combine the measurements of redundant sensors.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Aggregation {
+    /// Use the median of the remaining sensors' measurements.
+    Median,
+    /// Use the mean of the remaining sensors' measurements.
+    Mean,
+}
+
+/// Maximum deviation of a single sensor from the median of all sensors.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Tolerance {
+    /// The maximum temperature deviation in degree Celsius (°C).
+    pub temperature: I16F16,
+    /// The maximum relative humidity deviation in percent (%).
+    pub humidity: I16F16,
+}
+
+/// Outcome for a single sensor of an aggregated measurement.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Vote {
+    /// The sensor's measurement was used for the aggregated measurement.
+    Accepted,
+    /// The sensor's measurement deviated beyond the tolerance and got excluded.
+    VotedOut,
+    /// Measuring with the sensor failed.
+    Failed,
+}
+
+/// A measurement combined from redundant sensors.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AggregatedMeasurement<const N: usize> {
+    /// The combined measurement.
+    pub measurement: Measurement,
+    /// The outcome for each sensor in the order of the sensors passed at construction.
+    pub votes: [Vote; N],
+    /// The serial numbers of the sensors in the order of the sensors passed at construction.
+    pub serial_numbers: [u32; N],
+}
+
+impl<const N: usize> AggregatedMeasurement<N> {
+    /// Returns the serial numbers of the sensors which got voted out.
+    pub fn voted_out(&self) -> impl Iterator<Item = u32> + '_ {
+        self.votes
+            .iter()
+            .zip(self.serial_numbers.iter())
+            .filter(|(vote, _)| **vote == Vote::VotedOut)
+            .map(|(_, serial_number)| *serial_number)
+    }
+}
+
+/// Combines the measurements of two or three redundant sensors into a single one.
+///
+/// Every sensor's measurement is compared against the median of all sensors. Sensors deviating
+/// beyond the configured [`Tolerance`] get voted out and the remaining ones get combined
+/// according to the configured [`Aggregation`]. The remaining sensors need to form a strict
+/// majority of the successfully measuring sensors. Otherwise there is no consensus. So two
+/// disagreeing sensors result in [`RedundancyError::NoConsensus`] as it is not possible to tell
+/// which one is faulty.
+#[derive(Debug)]
+pub struct RedundantSensors<I, D, const N: usize, A = Address> {
+    group: Sht4xGroup<I, D, N, A>,
+    serial_numbers: [u32; N],
+    aggregation: Aggregation,
+    tolerance: Tolerance,
+}
+
+impl<I, D, const N: usize, A> RedundantSensors<I, D, N, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new instance from the given group of sensors.
+    ///
+    /// This reads the serial numbers of all sensors for reporting them along with the votes.
+    /// Fails with the error of the first failing sensor if any of them could not be read.
+    pub fn new(
+        mut group: Sht4xGroup<I, D, N, A>,
+        aggregation: Aggregation,
+        tolerance: Tolerance,
+        delay: &mut D,
+    ) -> Result<Self, Error<I::Error>> {
+        let mut serial_numbers = [0; N];
+        for (serial_number, result) in serial_numbers.iter_mut().zip(group.serial_numbers(delay)) {
+            *serial_number = result?;
+        }
+
+        Ok(Self {
+            group,
+            serial_numbers,
+            aggregation,
+            tolerance,
+        })
+    }
+
+    /// Destroys the instance and returns the group of sensors.
+    pub fn destroy(self) -> Sht4xGroup<I, D, N, A> {
+        self.group
+    }
+
+    /// Performs a measurement with every sensor and combines them into a single one.
+    ///
+    /// Fails with the error of the last failing sensor if no sensor could be measured at all.
+    pub fn measure(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+    ) -> Result<AggregatedMeasurement<N>, RedundancyError<I::Error>> {
+        let results = self.group.measure(precision, delay);
+
+        let mut votes = [Vote::Failed; N];
+        let mut temperatures = [I16F16::ZERO; N];
+        let mut humidities = [I16F16::ZERO; N];
+        let mut count = 0;
+        let mut last_error = None;
+
+        for (result, vote) in results.into_iter().zip(votes.iter_mut()) {
+            match result {
+                Ok(measurement) => {
+                    temperatures[count] = measurement.temperature_celsius();
+                    humidities[count] = measurement.humidity_percent();
+                    count += 1;
+                    *vote = Vote::Accepted;
+                }
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        if count == 0 {
+            return Err(last_error.map_or(RedundancyError::NoConsensus, RedundancyError::Sensor));
+        }
+
+        // Sort copies for determining the medians as the original order is needed for voting.
+        let mut sorted_temperatures = temperatures;
+        let mut sorted_humidities = humidities;
+        let median_temperature = median(&mut sorted_temperatures[..count]);
+        let median_humidity = median(&mut sorted_humidities[..count]);
+
+        let mut accepted = 0;
+        let measured = votes
+            .iter_mut()
+            .filter(|vote| **vote == Vote::Accepted)
+            .enumerate();
+        for (index, vote) in measured {
+            let deviates = (temperatures[index] - median_temperature).abs()
+                > self.tolerance.temperature
+                || (humidities[index] - median_humidity).abs() > self.tolerance.humidity;
+
+            if deviates {
+                *vote = Vote::VotedOut;
+            } else {
+                temperatures[accepted] = temperatures[index];
+                humidities[accepted] = humidities[index];
+                accepted += 1;
+            }
+        }
+
+        if 2 * accepted <= count {
+            return Err(RedundancyError::NoConsensus);
+        }
+
+        let temperatures = &mut temperatures[..accepted];
+        let humidities = &mut humidities[..accepted];
+        let measurement = match self.aggregation {
+            Aggregation::Median => Measurement::new(median(temperatures), median(humidities)),
+            Aggregation::Mean => Measurement::new(mean(temperatures), mean(humidities)),
+        };
+
+        Ok(AggregatedMeasurement {
+            measurement,
+            votes,
+            serial_numbers: self.serial_numbers,
+        })
+    }
+}
+
+fn mean(values: &[I16F16]) -> I16F16 {
+    let sum: I48F16 = values.iter().map(|value| I48F16::from_num(*value)).sum();
+    (sum / values.len() as i64).to_num()
+}
@@ -2,6 +2,7 @@ use crate::types::SensorData;
 
 const PAYLOAD_LEN: usize = 4;
 pub(crate) const RESPONSE_LEN: usize = 6;
+pub(crate) const TEMPERATURE_RESPONSE_LEN: usize = 3;
 
 fn response_payload(response: [u8; RESPONSE_LEN]) -> [u8; PAYLOAD_LEN] {
     // Response data comes in chunks of three bytes: [MSB, LSB, CRC]. The CRCs got already checked
@@ -21,3 +22,8 @@ pub(crate) fn serial_number_from_response(response: [u8; RESPONSE_LEN]) -> u32 {
     let payload = response_payload(response);
     u32::from_be_bytes(payload)
 }
+
+pub(crate) fn temperature_from_response(response: [u8; TEMPERATURE_RESPONSE_LEN]) -> u16 {
+    // STS4x sensors only respond with a single chunk [MSB, LSB, CRC] for the temperature.
+    u16::from_be_bytes([response[0], response[1]])
+}
@@ -0,0 +1,284 @@
+use crate::{
+    error::Error,
+    sht4x::Sht4x,
+    types::{Address, HeatingDuration, HeatingPower, Measurement, Precision, SensorAddress},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+#[cfg(feature = "embedded-hal-async")]
+use crate::sht4x_async::Sht4xAsync;
+
+/// Configuration for [`MeasurementScheduler`] and `MeasurementSchedulerAsync`.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SchedulerConfig {
+    /// The precision to use for normal measurements.
+    pub precision: Precision,
+    /// The time between two consecutive normal measurements in milliseconds.
+    pub interval_ms: u32,
+    /// The heating power of the heater cycles.
+    pub heating_power: HeatingPower,
+    /// The heating duration of the heater cycles.
+    pub heating_duration: HeatingDuration,
+    /// The time between two consecutive heater cycles in milliseconds. Zero disables the heater
+    /// cycles.
+    pub heating_interval_ms: u32,
+    /// The time after the end of a heater cycle in milliseconds during which normal measurements
+    /// are suppressed as the sensor is still warm.
+    pub cool_down_ms: u32,
+}
+
+impl Default for SchedulerConfig {
+    /// High-precision measurements every second and a 200 mW heater cycle of 1 s every ten minutes
+    /// with a cool-down of 30 s.
+    fn default() -> Self {
+        Self {
+            precision: Precision::High,
+            interval_ms: 1000,
+            heating_power: HeatingPower::High,
+            heating_duration: HeatingDuration::Long,
+            heating_interval_ms: 600_000,
+            cool_down_ms: 30_000,
+        }
+    }
+}
+
+/// A measurement taken by [`MeasurementScheduler`] or `MeasurementSchedulerAsync`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ScheduledMeasurement {
+    /// A normal measurement reflecting the environment.
+    Normal(Measurement),
+    /// A measurement taken at the end of a heater cycle. It does not reflect the environment.
+    Heated(Measurement),
+}
+
+impl ScheduledMeasurement {
+    /// Returns the measurement regardless of its kind.
+    pub fn measurement(&self) -> Measurement {
+        match self {
+            ScheduledMeasurement::Normal(measurement) => *measurement,
+            ScheduledMeasurement::Heated(measurement) => *measurement,
+        }
+    }
+
+    /// Returns whether this is a heated measurement.
+    pub fn is_heated(&self) -> bool {
+        matches!(self, ScheduledMeasurement::Heated(_))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Action {
+    Measure,
+    Heat,
+}
+
+/// The schedule shared by the blocking and async schedulers.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Schedule {
+    config: SchedulerConfig,
+    next_measurement_ms: u64,
+    next_heating_ms: u64,
+    cool_down_until_ms: u64,
+}
+
+impl Schedule {
+    fn new(config: SchedulerConfig) -> Self {
+        Self {
+            config,
+            next_measurement_ms: 0,
+            next_heating_ms: u64::from(config.heating_interval_ms),
+            cool_down_until_ms: 0,
+        }
+    }
+
+    fn heating_enabled(&self) -> bool {
+        self.config.heating_interval_ms != 0
+    }
+
+    fn next_due_ms(&self) -> u64 {
+        let next_measurement_ms = self.next_measurement_ms.max(self.cool_down_until_ms);
+        if self.heating_enabled() {
+            next_measurement_ms.min(self.next_heating_ms)
+        } else {
+            next_measurement_ms
+        }
+    }
+
+    /// Determines the action due at the given time and advances the schedule.
+    ///
+    /// The cool-down window after a heater cycle starts when the heater cycle taking `heating_ms`
+    /// has completed.
+    fn advance(&mut self, now_ms: u64, heating_ms: u32) -> Option<Action> {
+        if self.heating_enabled() && now_ms >= self.next_heating_ms {
+            self.next_heating_ms = next(
+                self.next_heating_ms,
+                self.config.heating_interval_ms,
+                now_ms,
+            );
+            self.cool_down_until_ms =
+                now_ms + u64::from(heating_ms) + u64::from(self.config.cool_down_ms);
+            return Some(Action::Heat);
+        }
+
+        if now_ms >= self.next_measurement_ms {
+            self.next_measurement_ms =
+                next(self.next_measurement_ms, self.config.interval_ms, now_ms);
+            if now_ms >= self.cool_down_until_ms {
+                return Some(Action::Measure);
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the next due time after `now_ms` keeping the phase of the schedule if possible.
+fn next(due_ms: u64, interval_ms: u32, now_ms: u64) -> u64 {
+    let next_ms = due_ms + u64::from(interval_ms);
+    if next_ms > now_ms {
+        next_ms
+    } else {
+        now_ms + u64::from(interval_ms)
+    }
+}
+
+/// Runs periodic measurements interleaved with heater cycles.
+///
+/// The scheduler is driven by calling [`tick`](Self::tick) with a monotonic timestamp, for
+/// example from a free-running timer. It performs a normal measurement every
+/// [`SchedulerConfig::interval_ms`] and a heater cycle every
+/// [`SchedulerConfig::heating_interval_ms`]. Normal measurements falling into the cool-down
+/// window after a heater cycle are skipped. So heated readings are never mistaken for normal
+/// ones.
+///
+/// **Note:** The heater is designed to be used up to 10 % of the sensor's lifetime. Please check
+/// the
+/// [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf),
+/// section 4.9 _Heater Operation_ for details.
+#[derive(Debug)]
+pub struct MeasurementScheduler<I, D, A = Address> {
+    sensor: Sht4x<I, D, A>,
+    schedule: Schedule,
+}
+
+impl<I, D, A> MeasurementScheduler<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new scheduler for the given driver. The first normal measurement is due
+    /// immediately and the first heater cycle after one heating interval.
+    pub fn new(sensor: Sht4x<I, D, A>, config: SchedulerConfig) -> Self {
+        Self {
+            sensor,
+            schedule: Schedule::new(config),
+        }
+    }
+
+    /// Destroys the scheduler and returns the driver.
+    pub fn destroy(self) -> Sht4x<I, D, A> {
+        self.sensor
+    }
+
+    /// Returns the time in milliseconds at which the next tick has something to do.
+    pub fn next_due_ms(&self) -> u64 {
+        self.schedule.next_due_ms()
+    }
+
+    /// Performs the measurement or heater cycle due at the given time in milliseconds, if any.
+    ///
+    /// A failed measurement or heater cycle is not retried before its next regular due time.
+    pub fn tick(
+        &mut self,
+        now_ms: u64,
+        delay: &mut D,
+    ) -> Result<Option<ScheduledMeasurement>, Error<I::Error>> {
+        let heating_ms = self
+            .sensor
+            .timings()
+            .heating_ms(self.schedule.config.heating_duration);
+        let measurement = match self.schedule.advance(now_ms, heating_ms) {
+            Some(Action::Measure) => ScheduledMeasurement::Normal(
+                self.sensor.measure(self.schedule.config.precision, delay)?,
+            ),
+            Some(Action::Heat) => ScheduledMeasurement::Heated(self.sensor.heat_and_measure(
+                self.schedule.config.heating_power,
+                self.schedule.config.heating_duration,
+                delay,
+            )?),
+            None => return Ok(None),
+        };
+
+        Ok(Some(measurement))
+    }
+}
+
+/// Async version of [`MeasurementScheduler`].
+#[cfg(feature = "embedded-hal-async")]
+#[derive(Debug)]
+pub struct MeasurementSchedulerAsync<I, D, A = Address> {
+    sensor: Sht4xAsync<I, D, A>,
+    schedule: Schedule,
+}
+
+#[cfg(feature = "embedded-hal-async")]
+impl<I, D, A> MeasurementSchedulerAsync<I, D, A>
+where
+    I: embedded_hal_async::i2c::I2c,
+    D: embedded_hal_async::delay::DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new scheduler for the given driver. The first normal measurement is due
+    /// immediately and the first heater cycle after one heating interval.
+    pub fn new(sensor: Sht4xAsync<I, D, A>, config: SchedulerConfig) -> Self {
+        Self {
+            sensor,
+            schedule: Schedule::new(config),
+        }
+    }
+
+    /// Destroys the scheduler and returns the driver.
+    pub fn destroy(self) -> Sht4xAsync<I, D, A> {
+        self.sensor
+    }
+
+    /// Returns the time in milliseconds at which the next tick has something to do.
+    pub fn next_due_ms(&self) -> u64 {
+        self.schedule.next_due_ms()
+    }
+
+    /// Performs the measurement or heater cycle due at the given time in milliseconds, if any.
+    ///
+    /// A failed measurement or heater cycle is not retried before its next regular due time.
+    pub async fn tick(
+        &mut self,
+        now_ms: u64,
+        delay: &mut D,
+    ) -> Result<Option<ScheduledMeasurement>, Error<I::Error>> {
+        let heating_ms = self
+            .sensor
+            .timings()
+            .heating_ms(self.schedule.config.heating_duration);
+        let measurement = match self.schedule.advance(now_ms, heating_ms) {
+            Some(Action::Measure) => ScheduledMeasurement::Normal(
+                self.sensor
+                    .measure(self.schedule.config.precision, delay)
+                    .await?,
+            ),
+            Some(Action::Heat) => ScheduledMeasurement::Heated(
+                self.sensor
+                    .heat_and_measure(
+                        self.schedule.config.heating_power,
+                        self.schedule.config.heating_duration,
+                        delay,
+                    )
+                    .await?,
+            ),
+            None => return Ok(None),
+        };
+
+        Ok(Some(measurement))
+    }
+}
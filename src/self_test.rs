@@ -0,0 +1,124 @@
+use crate::{
+    error::Error,
+    sht4x::Sht4x,
+    types::{HeatingDuration, HeatingPower, Measurement, Precision, SensorAddress},
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use fixed::types::I16F16;
+
+/// Expected reaction of the sensor to a heater pulse.
+///
+/// The temperature rise is the heated measurement's temperature minus the baseline temperature.
+/// The humidity drop is the baseline humidity minus the heated measurement's humidity.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SelfTestLimits {
+    /// The minimum temperature rise in degree Celsius (°C).
+    pub min_temperature_rise: I16F16,
+    /// The maximum temperature rise in degree Celsius (°C).
+    pub max_temperature_rise: I16F16,
+    /// The minimum relative humidity drop in percent (%).
+    pub min_humidity_drop: I16F16,
+    /// The maximum relative humidity drop in percent (%).
+    pub max_humidity_drop: I16F16,
+}
+
+impl SelfTestLimits {
+    /// Returns conservative limits for the given heater setting.
+    ///
+    /// The minimum temperature rise is chosen well below what the heater achieves at room
+    /// temperature in still air. The humidity drop is only required to be non-negative as there is
+    /// nothing to drop in a dry environment. Tighten the limits for your product's enclosure and
+    /// operating conditions.
+    pub fn for_heating(power: HeatingPower, duration: HeatingDuration) -> Self {
+        let min_temperature_rise = match (power, duration) {
+            (HeatingPower::Low, HeatingDuration::Short) => I16F16::from_num(0.2),
+            (HeatingPower::Low, HeatingDuration::Long) => I16F16::from_num(1),
+            (HeatingPower::Medium, HeatingDuration::Short) => I16F16::from_num(2),
+            (HeatingPower::Medium, HeatingDuration::Long) => I16F16::from_num(10),
+            (HeatingPower::High, HeatingDuration::Short) => I16F16::from_num(4),
+            (HeatingPower::High, HeatingDuration::Long) => I16F16::from_num(20),
+        };
+
+        Self {
+            min_temperature_rise,
+            max_temperature_rise: I16F16::from_num(130),
+            min_humidity_drop: I16F16::ZERO,
+            max_humidity_drop: I16F16::from_num(100),
+        }
+    }
+}
+
+/// Outcome of a heater-based self-test.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SelfTestReport {
+    /// The measurement before activating the heater.
+    pub baseline: Measurement,
+    /// The measurement at the end of the heater pulse.
+    pub heated: Measurement,
+    /// The limits checked against.
+    pub limits: SelfTestLimits,
+}
+
+impl SelfTestReport {
+    /// Returns the observed temperature rise in degree Celsius (°C).
+    pub fn temperature_rise(&self) -> I16F16 {
+        self.heated.temperature_celsius() - self.baseline.temperature_celsius()
+    }
+
+    /// Returns the observed relative humidity drop in percent (%).
+    pub fn humidity_drop(&self) -> I16F16 {
+        self.baseline.humidity_percent() - self.heated.humidity_percent()
+    }
+
+    /// Returns whether the temperature rise is within the limits.
+    pub fn temperature_passed(&self) -> bool {
+        (self.limits.min_temperature_rise..=self.limits.max_temperature_rise)
+            .contains(&self.temperature_rise())
+    }
+
+    /// Returns whether the humidity drop is within the limits.
+    pub fn humidity_passed(&self) -> bool {
+        (self.limits.min_humidity_drop..=self.limits.max_humidity_drop)
+            .contains(&self.humidity_drop())
+    }
+
+    /// Returns whether the sensor passed the self-test.
+    pub fn passed(&self) -> bool {
+        self.temperature_passed() && self.humidity_passed()
+    }
+}
+
+impl<I, D, A> Sht4x<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Tests the sensor by stimulating it with its heater.
+    ///
+    /// This takes a high-precision baseline measurement and a heated one with the given heater
+    /// setting. The returned report tells whether the observed temperature rise and humidity drop
+    /// are within the given limits, for example from [`SelfTestLimits::for_heating`]. An I2C or
+    /// CRC error fails the self-test with an error instead.
+    ///
+    /// **Note:** The heater is designed to be used up to 10 % of the sensor's lifetime. Please
+    /// check the
+    /// [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf),
+    /// section 4.9 _Heater Operation_ for details.
+    pub fn self_test(
+        &mut self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+        limits: SelfTestLimits,
+        delay: &mut D,
+    ) -> Result<SelfTestReport, Error<I::Error>> {
+        let baseline = self.measure(Precision::High, delay)?;
+        let heated = self.heat_and_measure(power, duration, delay)?;
+
+        Ok(SelfTestReport {
+            baseline,
+            heated,
+            limits,
+        })
+    }
+}
@@ -1,19 +1,24 @@
 use crate::{
+    blocking,
     commands::Command,
     error::Error,
-    responses::{sensor_data_from_response, serial_number_from_response, RESPONSE_LEN},
-    types::{Address, HeatingDuration, HeatingPower, Measurement, Precision, SensorData},
+    responses::{sensor_data_from_response, RESPONSE_LEN},
+    sht4x_with_delay::Sht4xWithDelay,
+    types::{
+        Address, AveragedMeasurement, Averager, HeatingDuration, HeatingPower, Measurement,
+        Precision, SensorAddress, SensorData, Timings,
+    },
 };
 use core::marker::PhantomData;
 use embedded_hal::{delay::DelayNs, i2c::I2c};
-use sensirion_i2c::i2c;
 
 /// Driver for STH4x sensors.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, Eq, Hash, PartialEq)]
-pub struct Sht4x<I, D> {
+pub struct Sht4x<I, D, A = Address> {
     i2c: I,
-    address: Address,
+    address: A,
+    timings: Timings,
     // If we want to globally define the delay type for this struct, we have to consume the type
     // parameter.
     _delay: PhantomData<D>,
@@ -42,7 +47,7 @@ impl From<Precision> for Command {
     }
 }
 
-impl<I, D> Sht4x<I, D>
+impl<I, D> Sht4x<I, D, Address>
 where
     I: I2c,
     D: DelayNs,
@@ -56,19 +61,50 @@ where
         Self::new_with_address(i2c, Address::Address0x44)
     }
 
+    /// Creates a new driver instance using the given I2C bus and taking ownership of the given
+    /// delay. It configures the default I2C address 0x44 used by most family members.
+    ///
+    /// The returned driver's methods don't require passing a delay anymore.
+    pub fn new_with_delay(i2c: I, delay: D) -> Sht4xWithDelay<I, D> {
+        Sht4xWithDelay::new(i2c, delay)
+    }
+}
+
+impl<I, D, A> Sht4x<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
     /// Crates a new driver instance using the given I2C bus and address. This constructor allows
     /// to instantiate the driver for the SHT40-BD1B which uses the non-default I2C address 0x45.
     ///
+    /// The address is either an [`Address`] or a [`ConstAddress`](crate::ConstAddress) fixed at
+    /// compile time.
+    ///
     /// For operating multiple devices on the same bus,
     /// [`shared-bus`](https://github.com/Rahix/shared-bus) might come in handy.
-    pub fn new_with_address(i2c: I, address: Address) -> Self {
-        Sht4x {
+    pub fn new_with_address(i2c: I, address: A) -> Self {
+        Self {
             i2c,
             address,
+            timings: Timings::MAXIMUM,
             _delay: PhantomData,
         }
     }
 
+    /// Configures the given command execution times for this driver instance.
+    ///
+    /// The default is [`Timings::MAXIMUM`].
+    pub fn with_timings(self, timings: Timings) -> Self {
+        Self { timings, ..self }
+    }
+
+    /// Returns the command execution times used by this driver instance.
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
     /// Destroys the driver and returns the used I2C bus.
     pub fn destroy(self) -> I {
         self.i2c
@@ -80,6 +116,9 @@ where
     /// check the
     /// [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf),
     /// section 4.9 _Heater Operation_ for details.
+    ///
+    /// A long heating duration blocks for more than a second. Use a
+    /// [`ChunkedDelay`](crate::ChunkedDelay) for servicing a watchdog meanwhile.
     pub fn heat_and_measure(
         &mut self,
         power: HeatingPower,
@@ -112,6 +151,16 @@ where
         Ok(raw)
     }
 
+    /// Initializes the sensor after power-up and returns its serial number.
+    ///
+    /// This waits for the sensor's power-up time, performs a soft reset, and reads the serial
+    /// number twice for checking the sensor's presence. It fails with [`Error::NotPresent`] if
+    /// the sensor does not acknowledge its address and with [`Error::InconsistentResponse`] if
+    /// the serial numbers differ.
+    pub fn init(&mut self, delay: &mut D) -> Result<u32, Error<I::Error>> {
+        blocking::init(&mut self.i2c, self.address.into(), &self.timings, delay)
+    }
+
     /// Performs a measurement returning measurands in SI units.
     pub fn measure(
         &mut self,
@@ -122,6 +171,25 @@ where
         Ok(Measurement::from(raw))
     }
 
+    /// Performs `count` measurements and returns their average along with the spread of the raw
+    /// sensor data.
+    ///
+    /// Averaging several low-precision measurements might be cheaper than a single high-precision
+    /// one for reaching a given noise level. A count of zero is treated as one.
+    pub fn measure_averaged(
+        &mut self,
+        count: u16,
+        precision: Precision,
+        delay: &mut D,
+    ) -> Result<AveragedMeasurement, Error<I::Error>> {
+        let mut averager = Averager::new();
+        for _ in 0..count.max(1) {
+            averager.add(self.measure_raw(precision, delay)?);
+        }
+
+        Ok(averager.finish())
+    }
+
     /// Performs a measurement returning raw sensor data.
     pub fn measure_raw(
         &mut self,
@@ -139,9 +207,7 @@ where
 
     /// Reads the sensor's serial number.
     pub fn serial_number(&mut self, delay: &mut D) -> Result<u32, Error<I::Error>> {
-        self.write_command_and_delay_for_execution(Command::SerialNumber, delay)?;
-        let response = self.read_response()?;
-        Ok(serial_number_from_response(response))
+        blocking::serial_number(&mut self.i2c, self.address.into(), &self.timings, delay)
     }
 
     /// Performs a soft reset of the sensor.
@@ -149,12 +215,12 @@ where
         self.write_command_and_delay_for_execution(Command::SoftReset, delay)
     }
 
-    fn read_response(&mut self) -> Result<[u8; RESPONSE_LEN], Error<I::Error>> {
-        let mut response = [0; RESPONSE_LEN];
-
-        i2c::read_words_with_crc(&mut self.i2c, self.address.into(), &mut response)?;
+    pub(crate) fn read_response(&mut self) -> Result<[u8; RESPONSE_LEN], Error<I::Error>> {
+        blocking::read_response(&mut self.i2c, self.address.into())
+    }
 
-        Ok(response)
+    pub(crate) fn write_command(&mut self, command: Command) -> Result<(), Error<I::Error>> {
+        blocking::write_command(&mut self.i2c, self.address.into(), command)
     }
 
     fn write_command_and_delay_for_execution(
@@ -162,11 +228,12 @@ where
         command: Command,
         delay: &mut D,
     ) -> Result<(), Error<I::Error>> {
-        let code = command.code();
-
-        i2c::write_command_u8(&mut self.i2c, self.address.into(), code).map_err(Error::I2c)?;
-        delay.delay_ms(command.duration_ms());
-
-        Ok(())
+        blocking::write_command_and_delay_for_execution(
+            &mut self.i2c,
+            self.address.into(),
+            command,
+            &self.timings,
+            delay,
+        )
     }
 }
@@ -2,9 +2,13 @@ use crate::{
     commands::Command,
     error::Error,
     responses::{sensor_data_from_response, serial_number_from_response, RESPONSE_LEN},
-    types::{Address, HeatingDuration, HeatingPower, Measurement, Precision, SensorData},
+    types::{
+        Address, AveragedMeasurement, Averager, HeatingDuration, HeatingPower, Measurement,
+        Precision, SensorAddress, SensorData, Timings,
+    },
 };
-use core::marker::PhantomData;
+use core::{future::Future, marker::PhantomData};
+use embassy_futures::select::{select, Either};
 use embedded_hal_async::{delay::DelayNs, i2c::I2c};
 use sensirion_i2c::i2c_async;
 
@@ -15,15 +19,69 @@ use sensirion_i2c::i2c_async;
 /// of the `embedded-hal` traits, and all of its methods are `async fn`s.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug)]
-pub struct Sht4xAsync<I, D> {
+pub struct Sht4xAsync<I, D, A = Address> {
     i2c: I,
-    address: Address,
+    address: A,
+    timings: Timings,
     // If we want to globally define the delay type for this struct, we have to consume the type
     // parameter.
     _delay: PhantomData<D>,
 }
 
-impl<I, D> Sht4xAsync<I, D>
+/// Deadline for the bus transactions and delays of [`Sht4xAsync`].
+///
+/// Every I2C transaction gets raced against `timeout_ms` measured by the given timer. Waiting for
+/// a command's execution gets raced against its execution time plus `timeout_ms`. This allows to
+/// detect a wedged bus with an async I2C implementation which never completes.
+///
+/// The timer needs to be an independent [`DelayNs`] instance as it runs concurrently to the
+/// driver's delay.
+#[derive(Debug)]
+pub struct Timeout<T> {
+    timer: T,
+    timeout_ms: u32,
+}
+
+impl<T: DelayNs> Timeout<T> {
+    /// Creates a new timeout using the given timer and deadline in milliseconds.
+    pub fn new(timer: T, timeout_ms: u32) -> Self {
+        Self { timer, timeout_ms }
+    }
+
+    /// Destroys the timeout and returns the used timer.
+    pub fn destroy(self) -> T {
+        self.timer
+    }
+}
+
+/// Races futures of bus transactions and delays against a deadline.
+trait Deadline {
+    async fn race<F: Future>(&mut self, extra_ms: u32, future: F) -> Option<F::Output>;
+}
+
+/// A deadline which never expires.
+struct NoTimeout;
+
+impl Deadline for NoTimeout {
+    async fn race<F: Future>(&mut self, _extra_ms: u32, future: F) -> Option<F::Output> {
+        Some(future.await)
+    }
+}
+
+impl<T: DelayNs> Deadline for Timeout<T> {
+    async fn race<F: Future>(&mut self, extra_ms: u32, future: F) -> Option<F::Output> {
+        let deadline = self
+            .timer
+            .delay_ms(self.timeout_ms.saturating_add(extra_ms));
+
+        match select(future, deadline).await {
+            Either::First(output) => Some(output),
+            Either::Second(()) => None,
+        }
+    }
+}
+
+impl<I, D> Sht4xAsync<I, D, Address>
 where
     I: I2c,
     D: DelayNs,
@@ -36,20 +94,43 @@ where
     pub fn new(i2c: I) -> Self {
         Self::new_with_address(i2c, Address::Address0x44)
     }
+}
 
+impl<I, D, A> Sht4xAsync<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
     /// Crates a new driver instance using the given I2C bus and address. This constructor allows
     /// to instantiate the driver for the SHT40-BD1B which uses the non-default I2C address 0x45.
     ///
+    /// The address is either an [`Address`] or a [`ConstAddress`](crate::ConstAddress) fixed at
+    /// compile time.
+    ///
     /// For operating multiple devices on the same bus,
     /// [`shared-bus`](https://github.com/Rahix/shared-bus) might come in handy.
-    pub fn new_with_address(i2c: I, address: Address) -> Self {
+    pub fn new_with_address(i2c: I, address: A) -> Self {
         Self {
             i2c,
             address,
+            timings: Timings::MAXIMUM,
             _delay: PhantomData,
         }
     }
 
+    /// Configures the given command execution times for this driver instance.
+    ///
+    /// The default is [`Timings::MAXIMUM`].
+    pub fn with_timings(self, timings: Timings) -> Self {
+        Self { timings, ..self }
+    }
+
+    /// Returns the command execution times used by this driver instance.
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
     /// Destroys the driver and returns the used I2C bus.
     pub fn destroy(self) -> I {
         self.i2c
@@ -72,6 +153,22 @@ where
         Ok(Measurement::from(raw))
     }
 
+    /// Like [`heat_and_measure`](Self::heat_and_measure) but fails with [`Error::Timeout`] if a
+    /// bus transaction or delay does not complete before the given deadline.
+    pub async fn heat_and_measure_with_timeout<T: DelayNs>(
+        &mut self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+        delay: &mut D,
+        timeout: &mut Timeout<T>,
+    ) -> Result<Measurement, Error<I::Error>> {
+        let raw = self
+            .heat_and_measure_raw_with_timeout(power, duration, delay, timeout)
+            .await?;
+
+        Ok(Measurement::from(raw))
+    }
+
     /// Activates the heater and performs a measurement returning raw sensor data.
     ///
     /// **Note:** The heater is designed to be used up to 10 % of the sensor's lifetime. Please
@@ -85,13 +182,43 @@ where
         delay: &mut D,
     ) -> Result<SensorData, Error<I::Error>> {
         let command = Command::from((power, duration));
+        self.execute_sensor_data_command(command, delay, &mut NoTimeout)
+            .await
+    }
 
-        self.write_command_and_delay_for_execution(command, delay)
-            .await?;
-        let response = self.read_response().await?;
-        let raw = sensor_data_from_response(response);
+    /// Like [`heat_and_measure_raw`](Self::heat_and_measure_raw) but fails with
+    /// [`Error::Timeout`] if a bus transaction or delay does not complete before the given
+    /// deadline.
+    pub async fn heat_and_measure_raw_with_timeout<T: DelayNs>(
+        &mut self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+        delay: &mut D,
+        timeout: &mut Timeout<T>,
+    ) -> Result<SensorData, Error<I::Error>> {
+        let command = Command::from((power, duration));
+        self.execute_sensor_data_command(command, delay, timeout)
+            .await
+    }
 
-        Ok(raw)
+    /// Initializes the sensor after power-up and returns its serial number.
+    ///
+    /// This waits for the sensor's power-up time, performs a soft reset, and reads the serial
+    /// number twice for checking the sensor's presence. It fails with [`Error::NotPresent`] if
+    /// the sensor does not acknowledge its address and with [`Error::InconsistentResponse`] if
+    /// the serial numbers differ.
+    pub async fn init(&mut self, delay: &mut D) -> Result<u32, Error<I::Error>> {
+        delay.delay_ms(self.timings.power_up_ms).await;
+
+        self.soft_reset(delay)
+            .await
+            .map_err(Error::absent_on_nack)?;
+        let serial_number = self.serial_number(delay).await?;
+        if self.serial_number(delay).await? != serial_number {
+            return Err(Error::InconsistentResponse);
+        }
+
+        Ok(serial_number)
     }
 
     /// Performs a measurement returning measurands in SI units.
@@ -104,6 +231,47 @@ where
         Ok(Measurement::from(raw))
     }
 
+    /// Like [`measure`](Self::measure) but fails with [`Error::Timeout`] if a bus transaction or
+    /// delay does not complete before the given deadline.
+    pub async fn measure_with_timeout<T: DelayNs>(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+        timeout: &mut Timeout<T>,
+    ) -> Result<Measurement, Error<I::Error>> {
+        let raw = self
+            .measure_raw_with_timeout(precision, delay, timeout)
+            .await?;
+        Ok(Measurement::from(raw))
+    }
+
+    /// Performs `count` measurements and returns their average along with the spread of the raw
+    /// sensor data.
+    ///
+    /// See [`Sht4x::measure_averaged`](crate::Sht4x::measure_averaged) for details.
+    pub async fn measure_averaged(
+        &mut self,
+        count: u16,
+        precision: Precision,
+        delay: &mut D,
+    ) -> Result<AveragedMeasurement, Error<I::Error>> {
+        self.average_measurements(count, precision, delay, &mut NoTimeout)
+            .await
+    }
+
+    /// Like [`measure_averaged`](Self::measure_averaged) but fails with [`Error::Timeout`] if a
+    /// bus transaction or delay does not complete before the given deadline.
+    pub async fn measure_averaged_with_timeout<T: DelayNs>(
+        &mut self,
+        count: u16,
+        precision: Precision,
+        delay: &mut D,
+        timeout: &mut Timeout<T>,
+    ) -> Result<AveragedMeasurement, Error<I::Error>> {
+        self.average_measurements(count, precision, delay, timeout)
+            .await
+    }
+
     /// Performs a measurement returning raw sensor data.
     pub async fn measure_raw(
         &mut self,
@@ -111,33 +279,112 @@ where
         delay: &mut D,
     ) -> Result<SensorData, Error<I::Error>> {
         let command = Command::from(precision);
+        self.execute_sensor_data_command(command, delay, &mut NoTimeout)
+            .await
+    }
 
-        self.write_command_and_delay_for_execution(command, delay)
-            .await?;
-        let response = self.read_response().await?;
-        let raw = sensor_data_from_response(response);
-
-        Ok(raw)
+    /// Like [`measure_raw`](Self::measure_raw) but fails with [`Error::Timeout`] if a bus
+    /// transaction or delay does not complete before the given deadline.
+    pub async fn measure_raw_with_timeout<T: DelayNs>(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+        timeout: &mut Timeout<T>,
+    ) -> Result<SensorData, Error<I::Error>> {
+        let command = Command::from(precision);
+        self.execute_sensor_data_command(command, delay, timeout)
+            .await
     }
 
     /// Reads the sensor's serial number.
     pub async fn serial_number(&mut self, delay: &mut D) -> Result<u32, Error<I::Error>> {
-        self.write_command_and_delay_for_execution(Command::SerialNumber, delay)
-            .await?;
-        let response = self.read_response().await?;
-        Ok(serial_number_from_response(response))
+        self.read_serial_number(delay, &mut NoTimeout).await
+    }
+
+    /// Like [`serial_number`](Self::serial_number) but fails with [`Error::Timeout`] if a bus
+    /// transaction or delay does not complete before the given deadline.
+    pub async fn serial_number_with_timeout<T: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout: &mut Timeout<T>,
+    ) -> Result<u32, Error<I::Error>> {
+        self.read_serial_number(delay, timeout).await
     }
 
     /// Performs a soft reset of the sensor.
     pub async fn soft_reset(&mut self, delay: &mut D) -> Result<(), Error<I::Error>> {
-        self.write_command_and_delay_for_execution(Command::SoftReset, delay)
+        self.write_command_and_delay_for_execution(Command::SoftReset, delay, &mut NoTimeout)
             .await
     }
 
-    async fn read_response(&mut self) -> Result<[u8; RESPONSE_LEN], Error<I::Error>> {
+    /// Like [`soft_reset`](Self::soft_reset) but fails with [`Error::Timeout`] if a bus
+    /// transaction or delay does not complete before the given deadline.
+    pub async fn soft_reset_with_timeout<T: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout: &mut Timeout<T>,
+    ) -> Result<(), Error<I::Error>> {
+        self.write_command_and_delay_for_execution(Command::SoftReset, delay, timeout)
+            .await
+    }
+
+    async fn average_measurements(
+        &mut self,
+        count: u16,
+        precision: Precision,
+        delay: &mut D,
+        deadline: &mut impl Deadline,
+    ) -> Result<AveragedMeasurement, Error<I::Error>> {
+        let command = Command::from(precision);
+        let mut averager = Averager::new();
+        for _ in 0..count.max(1) {
+            averager.add(
+                self.execute_sensor_data_command(command, delay, deadline)
+                    .await?,
+            );
+        }
+
+        Ok(averager.finish())
+    }
+
+    async fn execute_sensor_data_command(
+        &mut self,
+        command: Command,
+        delay: &mut D,
+        deadline: &mut impl Deadline,
+    ) -> Result<SensorData, Error<I::Error>> {
+        self.write_command_and_delay_for_execution(command, delay, deadline)
+            .await?;
+        let response = self.read_response(deadline).await?;
+        let raw = sensor_data_from_response(response);
+
+        Ok(raw)
+    }
+
+    async fn read_serial_number(
+        &mut self,
+        delay: &mut D,
+        deadline: &mut impl Deadline,
+    ) -> Result<u32, Error<I::Error>> {
+        self.write_command_and_delay_for_execution(Command::SerialNumber, delay, deadline)
+            .await?;
+        let response = self.read_response(deadline).await?;
+        Ok(serial_number_from_response(response))
+    }
+
+    async fn read_response(
+        &mut self,
+        deadline: &mut impl Deadline,
+    ) -> Result<[u8; RESPONSE_LEN], Error<I::Error>> {
         let mut response = [0; RESPONSE_LEN];
 
-        i2c_async::read_words_with_crc(&mut self.i2c, self.address.into(), &mut response).await?;
+        deadline
+            .race(
+                0,
+                i2c_async::read_words_with_crc(&mut self.i2c, self.address.into(), &mut response),
+            )
+            .await
+            .ok_or(Error::Timeout)??;
 
         Ok(response)
     }
@@ -146,13 +393,23 @@ where
         &mut self,
         command: Command,
         delay: &mut D,
+        deadline: &mut impl Deadline,
     ) -> Result<(), Error<I::Error>> {
         let code = command.code();
+        let duration_ms = command.duration_ms(&self.timings);
 
-        i2c_async::write_command_u8(&mut self.i2c, self.address.into(), code)
+        deadline
+            .race(
+                0,
+                i2c_async::write_command_u8(&mut self.i2c, self.address.into(), code),
+            )
             .await
+            .ok_or(Error::Timeout)?
             .map_err(Error::I2c)?;
-        delay.delay_ms(command.duration_ms()).await;
+        deadline
+            .race(duration_ms, delay.delay_ms(duration_ms))
+            .await
+            .ok_or(Error::Timeout)?;
 
         Ok(())
     }
@@ -0,0 +1,135 @@
+use crate::{
+    error::Error,
+    sht4x::Sht4x,
+    types::{
+        Address, AveragedMeasurement, HeatingDuration, HeatingPower, Measurement, Precision,
+        SensorAddress, SensorData, Timings,
+    },
+};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Driver for STH4x sensors which owns its delay.
+///
+/// This type behaves identically to the [`Sht4x`] type, except that it takes ownership of the
+/// delay at construction. So its methods don't need a delay argument. Use [`Sht4x`] for sharing
+/// a single delay among multiple drivers.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct Sht4xWithDelay<I, D, A = Address> {
+    sensor: Sht4x<I, D, A>,
+    delay: D,
+}
+
+impl<I, D> Sht4xWithDelay<I, D, Address>
+where
+    I: I2c,
+    D: DelayNs,
+{
+    /// Creates a new driver instance using the given I2C bus and delay. It configures the default
+    /// I2C address 0x44 used by most family members.
+    pub fn new(i2c: I, delay: D) -> Self {
+        Self::new_with_address(i2c, Address::Address0x44, delay)
+    }
+}
+
+impl<I, D, A> Sht4xWithDelay<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new driver instance using the given I2C bus, address, and delay.
+    ///
+    /// The address is either an [`Address`] or a [`ConstAddress`](crate::ConstAddress) fixed at
+    /// compile time.
+    pub fn new_with_address(i2c: I, address: A, delay: D) -> Self {
+        Self {
+            sensor: Sht4x::new_with_address(i2c, address),
+            delay,
+        }
+    }
+
+    /// Configures the given command execution times for this driver instance.
+    ///
+    /// The default is [`Timings::MAXIMUM`].
+    pub fn with_timings(self, timings: Timings) -> Self {
+        Self {
+            sensor: self.sensor.with_timings(timings),
+            ..self
+        }
+    }
+
+    /// Returns the command execution times used by this driver instance.
+    pub fn timings(&self) -> &Timings {
+        self.sensor.timings()
+    }
+
+    /// Destroys the driver and returns the used I2C bus and delay.
+    pub fn destroy(self) -> (I, D) {
+        (self.sensor.destroy(), self.delay)
+    }
+
+    /// Activates the heater and performs a measurement returning measurands in SI units.
+    ///
+    /// See [`Sht4x::heat_and_measure`] for details.
+    pub fn heat_and_measure(
+        &mut self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+    ) -> Result<Measurement, Error<I::Error>> {
+        self.sensor
+            .heat_and_measure(power, duration, &mut self.delay)
+    }
+
+    /// Activates the heater and performs a measurement returning raw sensor data.
+    ///
+    /// See [`Sht4x::heat_and_measure_raw`] for details.
+    pub fn heat_and_measure_raw(
+        &mut self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+    ) -> Result<SensorData, Error<I::Error>> {
+        self.sensor
+            .heat_and_measure_raw(power, duration, &mut self.delay)
+    }
+
+    /// Initializes the sensor after power-up and returns its serial number.
+    ///
+    /// See [`Sht4x::init`] for details.
+    pub fn init(&mut self) -> Result<u32, Error<I::Error>> {
+        self.sensor.init(&mut self.delay)
+    }
+
+    /// Performs a measurement returning measurands in SI units.
+    pub fn measure(&mut self, precision: Precision) -> Result<Measurement, Error<I::Error>> {
+        self.sensor.measure(precision, &mut self.delay)
+    }
+
+    /// Performs `count` measurements and returns their average along with the spread of the raw
+    /// sensor data.
+    ///
+    /// See [`Sht4x::measure_averaged`] for details.
+    pub fn measure_averaged(
+        &mut self,
+        count: u16,
+        precision: Precision,
+    ) -> Result<AveragedMeasurement, Error<I::Error>> {
+        self.sensor
+            .measure_averaged(count, precision, &mut self.delay)
+    }
+
+    /// Performs a measurement returning raw sensor data.
+    pub fn measure_raw(&mut self, precision: Precision) -> Result<SensorData, Error<I::Error>> {
+        self.sensor.measure_raw(precision, &mut self.delay)
+    }
+
+    /// Reads the sensor's serial number.
+    pub fn serial_number(&mut self) -> Result<u32, Error<I::Error>> {
+        self.sensor.serial_number(&mut self.delay)
+    }
+
+    /// Performs a soft reset of the sensor.
+    pub fn soft_reset(&mut self) -> Result<(), Error<I::Error>> {
+        self.sensor.soft_reset(&mut self.delay)
+    }
+}
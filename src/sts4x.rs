@@ -0,0 +1,131 @@
+use crate::{
+    blocking,
+    commands::Command,
+    error::Error,
+    responses::{temperature_from_response, TEMPERATURE_RESPONSE_LEN},
+    types::{Address, Precision, SensorAddress, TemperatureMeasurement, Timings},
+};
+use core::marker::PhantomData;
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+/// Driver for STS4x temperature sensors like the STS40 and STS45.
+///
+/// The STS4x family shares the command set and I2C framing with the SHT4x family but measures
+/// temperature only. It has no heater.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct Sts4x<I, D, A = Address> {
+    i2c: I,
+    address: A,
+    timings: Timings,
+    // If we want to globally define the delay type for this struct, we have to consume the type
+    // parameter.
+    _delay: PhantomData<D>,
+}
+
+impl<I, D> Sts4x<I, D, Address>
+where
+    I: I2c,
+    D: DelayNs,
+{
+    /// Creates a new driver instance using the given I2C bus. It configures the default I2C
+    /// address 0x44 used by the STS40-AD1B and STS45-AD1B.
+    pub fn new(i2c: I) -> Self {
+        Self::new_with_address(i2c, Address::Address0x44)
+    }
+}
+
+impl<I, D, A> Sts4x<I, D, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new driver instance using the given I2C bus and address.
+    ///
+    /// The address is either an [`Address`] or a [`ConstAddress`](crate::ConstAddress) fixed at
+    /// compile time.
+    pub fn new_with_address(i2c: I, address: A) -> Self {
+        Self {
+            i2c,
+            address,
+            timings: Timings::MAXIMUM,
+            _delay: PhantomData,
+        }
+    }
+
+    /// Configures the given command execution times for this driver instance.
+    ///
+    /// The default is [`Timings::MAXIMUM`]. The heater timings are not used by STS4x sensors.
+    pub fn with_timings(self, timings: Timings) -> Self {
+        Self { timings, ..self }
+    }
+
+    /// Returns the command execution times used by this driver instance.
+    pub fn timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    /// Destroys the driver and returns the used I2C bus.
+    pub fn destroy(self) -> I {
+        self.i2c
+    }
+
+    /// Initializes the sensor after power-up and returns its serial number.
+    ///
+    /// See [`Sht4x::init`](crate::Sht4x::init) for details.
+    pub fn init(&mut self, delay: &mut D) -> Result<u32, Error<I::Error>> {
+        blocking::init(&mut self.i2c, self.address.into(), &self.timings, delay)
+    }
+
+    /// Performs a measurement returning the temperature in SI units.
+    pub fn measure(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+    ) -> Result<TemperatureMeasurement, Error<I::Error>> {
+        let raw = self.measure_raw(precision, delay)?;
+        Ok(TemperatureMeasurement::from(raw))
+    }
+
+    /// Performs a measurement returning the raw temperature value.
+    pub fn measure_raw(
+        &mut self,
+        precision: Precision,
+        delay: &mut D,
+    ) -> Result<u16, Error<I::Error>> {
+        let command = Command::from(precision);
+
+        self.write_command_and_delay_for_execution(command, delay)?;
+        let response = blocking::read_response::<_, TEMPERATURE_RESPONSE_LEN>(
+            &mut self.i2c,
+            self.address.into(),
+        )?;
+
+        Ok(temperature_from_response(response))
+    }
+
+    /// Reads the sensor's serial number.
+    pub fn serial_number(&mut self, delay: &mut D) -> Result<u32, Error<I::Error>> {
+        blocking::serial_number(&mut self.i2c, self.address.into(), &self.timings, delay)
+    }
+
+    /// Performs a soft reset of the sensor.
+    pub fn soft_reset(&mut self, delay: &mut D) -> Result<(), Error<I::Error>> {
+        self.write_command_and_delay_for_execution(Command::SoftReset, delay)
+    }
+
+    fn write_command_and_delay_for_execution(
+        &mut self,
+        command: Command,
+        delay: &mut D,
+    ) -> Result<(), Error<I::Error>> {
+        blocking::write_command_and_delay_for_execution(
+            &mut self.i2c,
+            self.address.into(),
+            command,
+            &self.timings,
+            delay,
+        )
+    }
+}
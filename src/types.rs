@@ -20,18 +20,87 @@ impl From<Address> for u8 {
     }
 }
 
+/// I2C address fixed at compile time.
+///
+/// Using it with [`Sht4x::new_with_address`](crate::Sht4x::new_with_address) eliminates storing
+/// the address at runtime and makes it part of the driver's type, for example
+/// `Sht4x<I, D, ConstAddress<0x45>>`. This allows to check for address conflicts at compile time:
+///
+/// ```rust ignore
+/// type Inside = ConstAddress<0x44>;
+/// type Outside = ConstAddress<0x45>;
+/// const _: () = assert!(Inside::ADDRESS != Outside::ADDRESS);
+///
+/// let inside = Sht4x::new_with_address(i2c_inside, Inside::new());
+/// let outside = Sht4x::new_with_address(i2c_outside, Outside::new());
+/// ```
+///
+/// Creating a `ConstAddress` for an address not used by SHT4x sensors fails at compile time.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ConstAddress<const ADDRESS: u8>(());
+
+impl<const ADDRESS: u8> ConstAddress<ADDRESS> {
+    /// The address as raw value.
+    pub const ADDRESS: u8 = ADDRESS;
+
+    const VALID: () = assert!(
+        matches!(ADDRESS, 0x44..=0x46),
+        "not an I2C address of SHT4x sensors"
+    );
+
+    /// Creates the compile-time address.
+    pub const fn new() -> Self {
+        let () = Self::VALID;
+        Self(())
+    }
+}
+
+impl<const ADDRESS: u8> Default for ConstAddress<ADDRESS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const ADDRESS: u8> From<ConstAddress<ADDRESS>> for u8 {
+    fn from(_: ConstAddress<ADDRESS>) -> Self {
+        ADDRESS
+    }
+}
+
+/// Types providing the I2C address of a sensor.
+///
+/// This is either an [`Address`] determined at runtime or a [`ConstAddress`] fixed at compile
+/// time.
+pub trait SensorAddress: Copy + Into<u8> {}
+
+impl SensorAddress for Address {}
+
+impl<const ADDRESS: u8> SensorAddress for ConstAddress<ADDRESS> {}
+
 /// Heating power to apply when activating the internal heater.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum HeatingPower {
-    /// Operate the heater at 200 mW.
+    /// Operate the heater at 20 mW.
     Low,
     /// Operate the heater at 110 mW.
     Medium,
-    /// Operate the heater at 20 mW.
+    /// Operate the heater at 200 mW.
     High,
 }
 
+impl HeatingPower {
+    /// Returns the nominal heating power in milliwatts.
+    pub(crate) fn milliwatts(self) -> u32 {
+        match self {
+            HeatingPower::Low => 20,
+            HeatingPower::Medium => 110,
+            HeatingPower::High => 200,
+        }
+    }
+}
+
 /// Duration of heating when activating the internal heater.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -42,6 +111,16 @@ pub enum HeatingDuration {
     Long,
 }
 
+impl HeatingDuration {
+    /// Returns the nominal heating duration in milliseconds.
+    pub(crate) fn milliseconds(self) -> u32 {
+        match self {
+            HeatingDuration::Short => 100,
+            HeatingDuration::Long => 1000,
+        }
+    }
+}
+
 /// A measurement from the sensor in SI units.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Measurement {
@@ -51,6 +130,26 @@ pub struct Measurement {
     humidity: I16F16,
 }
 
+/// A temperature-only measurement from an STS4x sensor in SI units.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TemperatureMeasurement {
+    /// The measured temperature in degree Celsius (°C).
+    temperature: I16F16,
+}
+
+/// A measurement averaged from multiple conversions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AveragedMeasurement {
+    /// The averaged measurement.
+    pub measurement: Measurement,
+    /// The minimum raw temperature and humidity among the conversions.
+    pub min: SensorData,
+    /// The maximum raw temperature and humidity among the conversions.
+    pub max: SensorData,
+    /// The number of conversions.
+    pub count: u16,
+}
+
 /// The precision to request for a measurement.
 ///
 /// Higher-precision measurements take longer.
@@ -62,6 +161,62 @@ pub enum Precision {
     High,
 }
 
+/// Accumulates raw sensor data for an [`AveragedMeasurement`].
+pub(crate) struct Averager {
+    temperature_sum: u32,
+    humidity_sum: u32,
+    min: SensorData,
+    max: SensorData,
+    count: u16,
+}
+
+impl Averager {
+    pub(crate) fn new() -> Self {
+        Self {
+            temperature_sum: 0,
+            humidity_sum: 0,
+            min: SensorData {
+                temperature: u16::MAX,
+                humidity: u16::MAX,
+            },
+            max: SensorData {
+                temperature: 0,
+                humidity: 0,
+            },
+            count: 0,
+        }
+    }
+
+    pub(crate) fn add(&mut self, raw: SensorData) {
+        self.temperature_sum += u32::from(raw.temperature);
+        self.humidity_sum += u32::from(raw.humidity);
+        self.min.temperature = self.min.temperature.min(raw.temperature);
+        self.min.humidity = self.min.humidity.min(raw.humidity);
+        self.max.temperature = self.max.temperature.max(raw.temperature);
+        self.max.humidity = self.max.humidity.max(raw.humidity);
+        self.count += 1;
+    }
+
+    /// Returns the averaged measurement. There needs to be at least one added conversion.
+    pub(crate) fn finish(self) -> AveragedMeasurement {
+        let count = u32::from(self.count);
+        // Round to the nearest raw value. The averages of u16 values always fit into u16.
+        let average = |sum: u32| ((sum + count / 2) / count) as u16;
+
+        let raw = SensorData {
+            temperature: average(self.temperature_sum),
+            humidity: average(self.humidity_sum),
+        };
+
+        AveragedMeasurement {
+            measurement: Measurement::from(raw),
+            min: self.min,
+            max: self.max,
+            count: self.count,
+        }
+    }
+}
+
 /// A measurement from the sensor in raw sensor data.
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -72,6 +227,102 @@ pub struct SensorData {
     pub humidity: u16,
 }
 
+/// Execution times of the sensor's commands in milliseconds.
+///
+/// The driver waits for these durations before reading a command's response. Reading a response
+/// too early results in a NACK from the sensor.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Timings {
+    /// Duration of a measurement with [`Precision::High`].
+    pub measure_high_precision_ms: u32,
+    /// Duration of a measurement with [`Precision::Medium`].
+    pub measure_medium_precision_ms: u32,
+    /// Duration of a measurement with [`Precision::Low`].
+    pub measure_low_precision_ms: u32,
+    /// Duration of a heater measurement with [`HeatingDuration::Long`].
+    pub heat_long_ms: u32,
+    /// Duration of a heater measurement with [`HeatingDuration::Short`].
+    pub heat_short_ms: u32,
+    /// Duration for reading the serial number.
+    pub serial_number_ms: u32,
+    /// Duration of a soft reset.
+    pub soft_reset_ms: u32,
+    /// Time after power-up until the sensor accepts commands.
+    pub power_up_ms: u32,
+}
+
+impl Timings {
+    /// Values rounded up from the maximum durations given in the datasheet table 4, 'System
+    /// timing specifications'. This is the default.
+    pub const MAXIMUM: Self = Self {
+        measure_high_precision_ms: 9,
+        measure_medium_precision_ms: 5,
+        measure_low_precision_ms: 2,
+        heat_long_ms: 1100,
+        heat_short_ms: 110,
+        // There is no explicit time given for the serial number, but reading it immediately
+        // results in a NACK. So be a bit more patient here.
+        serial_number_ms: 1,
+        soft_reset_ms: 1,
+        power_up_ms: 1,
+    };
+
+    /// Values rounded up from the typical durations given in the datasheet table 4, 'System
+    /// timing specifications'.
+    ///
+    /// These save time and energy but some sensors might not have completed a command in time,
+    /// especially at the ends of the operating range.
+    pub const TYPICAL: Self = Self {
+        measure_high_precision_ms: 7,
+        measure_medium_precision_ms: 4,
+        measure_low_precision_ms: 2,
+        heat_long_ms: 1000,
+        heat_short_ms: 100,
+        serial_number_ms: 1,
+        soft_reset_ms: 1,
+        power_up_ms: 1,
+    };
+
+    /// Returns these timings with every duration extended by the given margin in percent.
+    pub const fn with_margin_percent(self, percent: u32) -> Self {
+        const fn extend(ms: u32, percent: u32) -> u32 {
+            let margin = (ms as u64 * percent as u64).div_ceil(100);
+            let extended = ms as u64 + margin;
+            if extended > u32::MAX as u64 {
+                u32::MAX
+            } else {
+                extended as u32
+            }
+        }
+
+        Self {
+            measure_high_precision_ms: extend(self.measure_high_precision_ms, percent),
+            measure_medium_precision_ms: extend(self.measure_medium_precision_ms, percent),
+            measure_low_precision_ms: extend(self.measure_low_precision_ms, percent),
+            heat_long_ms: extend(self.heat_long_ms, percent),
+            heat_short_ms: extend(self.heat_short_ms, percent),
+            serial_number_ms: extend(self.serial_number_ms, percent),
+            soft_reset_ms: extend(self.soft_reset_ms, percent),
+            power_up_ms: extend(self.power_up_ms, percent),
+        }
+    }
+
+    /// Returns the duration of a heater measurement with the given heating duration.
+    pub(crate) fn heating_ms(&self, duration: HeatingDuration) -> u32 {
+        match duration {
+            HeatingDuration::Long => self.heat_long_ms,
+            HeatingDuration::Short => self.heat_short_ms,
+        }
+    }
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self::MAXIMUM
+    }
+}
+
 #[cfg(feature = "defmt")]
 impl defmt::Format for Measurement {
     fn format(&self, f: defmt::Formatter) {
@@ -88,23 +339,44 @@ impl defmt::Format for Measurement {
     }
 }
 
+/// Converts a raw temperature value into degree Celsius (°C).
+fn celsius_from_raw(raw: u16) -> I16F16 {
+    const MINUS_45: I16F16 = I16F16::const_from_int(-45);
+
+    let quotient = U16F16::from_num(raw) / (u16::MAX as u32);
+    MINUS_45 + 175 * quotient.to_num::<I16F16>()
+}
+
+/// Converts a value into thousandths.
+fn milli(value: I16F16) -> i32 {
+    // Pre-scale to keep the multiplication to millis within the underlying
+    // i32 type.
+    let milli = value.to_num::<I18F14>() * 1000;
+    milli.to_num::<i32>()
+}
+
 impl From<SensorData> for Measurement {
     /// Converts raw sensor data into SI units.
     fn from(raw: SensorData) -> Self {
-        const MINUS_45: I16F16 = I16F16::const_from_int(-45);
         const MINUS_6: I16F16 = I16F16::const_from_int(-6);
 
-        let temperature_quotient = U16F16::from_num(raw.temperature) / (u16::MAX as u32);
         let humidity_quotient = U16F16::from_num(raw.humidity) / (u16::MAX as u32);
 
         Self {
-            temperature: MINUS_45 + 175 * temperature_quotient.to_num::<I16F16>(),
+            temperature: celsius_from_raw(raw.temperature),
             humidity: MINUS_6 + 125 * humidity_quotient.to_num::<I16F16>(),
         }
     }
 }
 
 impl Measurement {
+    pub(crate) fn new(temperature: I16F16, humidity: I16F16) -> Self {
+        Self {
+            temperature,
+            humidity,
+        }
+    }
+
     /// Returns the measured temperature in degree Celsius (°C).
     pub fn temperature_celsius(&self) -> I16F16 {
         self.temperature
@@ -113,18 +385,12 @@ impl Measurement {
     /// Returns the measured temperature in milli degree Celsius (m°C, a thousand of a degree
     /// Celsius).
     pub fn temperature_milli_celsius(&self) -> i32 {
-        // Pre-scale to keep the multiplication to millis within the underlying
-        // i32 type.
-        let milli = self.temperature.to_num::<I18F14>() * 1000;
-        milli.to_num::<i32>()
+        milli(self.temperature)
     }
 
     /// Returns the measured relative humidity in milli percent (m% RH, a thousand of a percent).
     pub fn humidity_milli_percent(&self) -> i32 {
-        // Pre-scale to keep the multiplication to millis within the underlying
-        // i32 type.
-        let milli = self.humidity.to_num::<I18F14>() * 1000;
-        milli.to_num::<i32>()
+        milli(self.humidity)
     }
 
     /// Returns the measured relative humidity in percent (%).
@@ -132,3 +398,36 @@ impl Measurement {
         self.humidity
     }
 }
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for TemperatureMeasurement {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "TemperatureMeasurement {{ {} m°C }}",
+            self.temperature_milli_celsius(),
+        );
+    }
+}
+
+impl From<u16> for TemperatureMeasurement {
+    /// Converts a raw temperature value from an STS4x sensor into SI units.
+    fn from(raw: u16) -> Self {
+        Self {
+            temperature: celsius_from_raw(raw),
+        }
+    }
+}
+
+impl TemperatureMeasurement {
+    /// Returns the measured temperature in degree Celsius (°C).
+    pub fn temperature_celsius(&self) -> I16F16 {
+        self.temperature
+    }
+
+    /// Returns the measured temperature in milli degree Celsius (m°C, a thousand of a degree
+    /// Celsius).
+    pub fn temperature_milli_celsius(&self) -> i32 {
+        milli(self.temperature)
+    }
+}
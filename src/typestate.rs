@@ -0,0 +1,242 @@
+//! Driver encoding the sensor's state in the type system.
+//!
+//! The SHT4x processes a single command at a time. It does not acknowledge its address while
+//! executing a command and a new command sent meanwhile fails with a NACK. Once a measurement has
+//! completed, a new command discards its response. The driver in this module only allows reading
+//! the response while a command is pending and only allows issuing a new command when there is
+//! no pending one. So issuing a command before reading the previous response gets caught at
+//! compile time.
+//!
+//! ```rust ignore
+//! use sht4x::typestate::Sht4x;
+//!
+//! let idle = Sht4x::new(i2c);
+//! let measuring = idle.start_measurement(Precision::High).map_err(|(_, e)| e)?;
+//! // Wait for measuring.duration_ms() or do something else in the meantime.
+//! let (measurement, idle) = measuring.read().map_err(|(_, e)| e)?;
+//! ```
+//!
+//! All transitions return the driver in its current state along with the error when they fail.
+//! So the I2C bus never gets lost.
+
+use crate::{
+    commands::Command,
+    error::Error,
+    responses::{sensor_data_from_response, serial_number_from_response, RESPONSE_LEN},
+    types::{Address, HeatingDuration, HeatingPower, Measurement, Precision, SensorAddress},
+};
+use core::marker::PhantomData;
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+
+mod sealed {
+    use crate::responses::RESPONSE_LEN;
+
+    pub trait Sealed {
+        type Output;
+
+        fn parse(response: [u8; RESPONSE_LEN]) -> Self::Output;
+    }
+}
+
+/// A command whose response can be read from the sensor.
+pub trait PendingCommand: sealed::Sealed {}
+
+/// A pending measurement, with or without heater.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Measure;
+
+impl sealed::Sealed for Measure {
+    type Output = Measurement;
+
+    fn parse(response: [u8; RESPONSE_LEN]) -> Self::Output {
+        Measurement::from(sensor_data_from_response(response))
+    }
+}
+
+impl PendingCommand for Measure {}
+
+/// A pending serial number read.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SerialNumber;
+
+impl sealed::Sealed for SerialNumber {
+    type Output = u32;
+
+    fn parse(response: [u8; RESPONSE_LEN]) -> Self::Output {
+        serial_number_from_response(response)
+    }
+}
+
+impl PendingCommand for SerialNumber {}
+
+/// State of a sensor ready for a new command.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Idle;
+
+/// State of a sensor executing a command whose response has not been read yet.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Measuring<C> {
+    command: Command,
+    _command: PhantomData<C>,
+}
+
+/// Driver for STH4x sensors with typestate.
+///
+/// See the [module documentation](self) for details.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct Sht4x<I, D, S = Idle, A = Address> {
+    sensor: crate::Sht4x<I, D, A>,
+    state: S,
+}
+
+/// Result of a state transition returning the driver in its previous state on error.
+pub type Transition<T, I, D, S, A = Address> = Result<
+    T,
+    (
+        Sht4x<I, D, S, A>,
+        Error<<I as embedded_hal::i2c::ErrorType>::Error>,
+    ),
+>;
+
+/// Result of issuing a command: the driver with the command pending.
+pub type Started<I, D, C, A = Address> = Transition<Sht4x<I, D, Measuring<C>, A>, I, D, Idle, A>;
+
+/// Result of reading a pending command's response: its output and the idle driver.
+pub type Completion<I, D, C, A = Address> =
+    Transition<(<C as sealed::Sealed>::Output, Sht4x<I, D, Idle, A>), I, D, Measuring<C>, A>;
+
+impl<I, D> Sht4x<I, D, Idle>
+where
+    I: I2c,
+    D: DelayNs,
+{
+    /// Creates a new driver instance using the given I2C bus. It configures the default I2C
+    /// address 0x44 used by most family members.
+    pub fn new(i2c: I) -> Self {
+        Self::from(crate::Sht4x::new(i2c))
+    }
+}
+
+impl<I, D, A> Sht4x<I, D, Idle, A>
+where
+    I: I2c,
+    D: DelayNs,
+    A: SensorAddress,
+{
+    /// Creates a new driver instance using the given I2C bus and address.
+    ///
+    /// The address is either an [`Address`] or a [`ConstAddress`](crate::ConstAddress) fixed at
+    /// compile time.
+    pub fn new_with_address(i2c: I, address: A) -> Self {
+        Self::from(crate::Sht4x::new_with_address(i2c, address))
+    }
+
+    /// Returns the driver without typestate.
+    pub fn into_inner(self) -> crate::Sht4x<I, D, A> {
+        self.sensor
+    }
+
+    /// Destroys the driver and returns the used I2C bus.
+    pub fn destroy(self) -> I {
+        self.sensor.destroy()
+    }
+
+    /// Starts a measurement.
+    pub fn start_measurement(self, precision: Precision) -> Started<I, D, Measure, A> {
+        self.start(Command::from(precision))
+    }
+
+    /// Activates the heater and starts a measurement.
+    ///
+    /// **Note:** The heater is designed to be used up to 10 % of the sensor's lifetime. Please
+    /// check the
+    /// [datasheet](https://sensirion.com/media/documents/33FD6951/624C4357/Datasheet_SHT4x.pdf),
+    /// section 4.9 _Heater Operation_ for details.
+    pub fn start_heated_measurement(
+        self,
+        power: HeatingPower,
+        duration: HeatingDuration,
+    ) -> Started<I, D, Measure, A> {
+        self.start(Command::from((power, duration)))
+    }
+
+    /// Starts reading the sensor's serial number.
+    pub fn start_serial_number(self) -> Started<I, D, SerialNumber, A> {
+        self.start(Command::SerialNumber)
+    }
+
+    fn start<C>(mut self, command: Command) -> Started<I, D, C, A> {
+        match self.sensor.write_command(command) {
+            Ok(()) => Ok(Sht4x {
+                sensor: self.sensor,
+                state: Measuring {
+                    command,
+                    _command: PhantomData,
+                },
+            }),
+            Err(e) => Err((self, e)),
+        }
+    }
+}
+
+impl<I, D, A> From<crate::Sht4x<I, D, A>> for Sht4x<I, D, Idle, A> {
+    /// Adds typestate to a driver which has no pending command.
+    fn from(sensor: crate::Sht4x<I, D, A>) -> Self {
+        Self {
+            sensor,
+            state: Idle,
+        }
+    }
+}
+
+impl<I, D, C, A> Sht4x<I, D, Measuring<C>, A>
+where
+    I: I2c,
+    D: DelayNs,
+    C: PendingCommand,
+    A: SensorAddress,
+{
+    /// Returns the time in milliseconds to wait before the response can be read.
+    pub fn duration_ms(&self) -> u32 {
+        self.state.command.duration_ms(self.sensor.timings())
+    }
+
+    /// Reads the response of the pending command.
+    ///
+    /// Reading the response before the command's [`duration_ms`](Self::duration_ms) elapsed
+    /// results in a NACK from the sensor. The driver stays in its measuring state on errors and
+    /// reading can be retried.
+    pub fn read(self) -> Completion<I, D, C, A> {
+        let mut sensor = self.sensor;
+
+        match sensor.read_response() {
+            Ok(response) => Ok((C::parse(response), Sht4x::from(sensor))),
+            Err(e) => Err((
+                Sht4x {
+                    sensor,
+                    state: self.state,
+                },
+                e,
+            )),
+        }
+    }
+
+    /// Discards the pending command without reading its response.
+    ///
+    /// This waits for the command to complete as the sensor does not acknowledge a new one before.
+    pub fn abort(self, delay: &mut D) -> Sht4x<I, D, Idle, A> {
+        delay.delay_ms(self.duration_ms());
+        Sht4x::from(self.sensor)
+    }
+
+    /// Waits for the pending command to complete and reads its response.
+    pub fn wait_and_read(self, delay: &mut D) -> Completion<I, D, C, A> {
+        delay.delay_ms(self.duration_ms());
+        self.read()
+    }
+}
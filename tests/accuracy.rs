@@ -0,0 +1,38 @@
+mod common;
+
+use common::measurement;
+use fixed::types::I16F16;
+use sht4x::Variant;
+
+#[test]
+fn accuracy_in_specified_range() {
+    // Roughly 25 °C and 50 %RH.
+    let measurement = measurement(26214, 29360);
+
+    let band = measurement.accuracy(Variant::Sht45);
+    assert_eq!(band.typical.temperature, I16F16::from_num(100) / 1000);
+    assert_eq!(band.typical.humidity, I16F16::from_num(1));
+    assert_eq!(band.maximum.temperature, I16F16::from_num(200) / 1000);
+    assert_eq!(band.maximum.humidity, I16F16::from_num(1500) / 1000);
+
+    let band = measurement.accuracy(Variant::Sht40);
+    assert_eq!(band.typical.temperature, I16F16::from_num(200) / 1000);
+    assert_eq!(band.typical.humidity, I16F16::from_num(1800) / 1000);
+}
+
+#[test]
+fn accuracy_gets_interpolated() {
+    // Roughly 77.5 °C in the middle between the support points at 65 °C and 90 °C.
+    let band = measurement(45875, 29360).accuracy(Variant::Sht40);
+
+    let expected = I16F16::from_num(250) / 1000;
+    assert!((band.typical.temperature - expected).abs() < I16F16::from_num(2) / 1000);
+}
+
+#[test]
+fn accuracy_gets_clamped() {
+    let band = measurement(0, u16::MAX).accuracy(Variant::Sht45);
+
+    assert_eq!(band.typical.temperature, I16F16::from_num(300) / 1000);
+    assert_eq!(band.maximum.humidity, I16F16::from_num(2));
+}
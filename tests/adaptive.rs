@@ -0,0 +1,48 @@
+mod common;
+
+use common::measure;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use sht4x::{AdaptiveConfig, AdaptiveSampler, Precision, Sht4x};
+
+#[test]
+fn speeds_up_on_change_and_backs_off() {
+    // Roughly 25 °C and 26 °C.
+    let (warm, warmer) = (26214, 26588);
+    let mut expectations = Vec::new();
+    expectations.extend(measure(0xe0, warm, 29360));
+    expectations.extend(measure(0xe0, warmer, 29360));
+    expectations.extend(measure(0xfd, warmer, 29360));
+    expectations.extend(measure(0xfd, warmer, 29360));
+    expectations.extend(measure(0xe0, warmer, 29360));
+    let mut i2c = Mock::new(&expectations);
+
+    let config = AdaptiveConfig {
+        back_off_after: 2,
+        ..Default::default()
+    };
+    let mut sampler = AdaptiveSampler::new(Sht4x::new(i2c.clone()), config);
+    let delay = &mut NoopDelay;
+
+    let sample = sampler.sample(0, delay).unwrap();
+    assert_eq!(sample.precision, Precision::Low);
+    assert_eq!(sample.next_interval_ms, 60_000);
+
+    // A change of 1 °C within a minute exceeds the default threshold.
+    let sample = sampler.sample(60_000, delay).unwrap();
+    assert_eq!(sample.precision, Precision::Low);
+    assert_eq!(sample.next_interval_ms, 5_000);
+    assert_eq!(sampler.precision(), Precision::High);
+
+    let sample = sampler.sample(65_000, delay).unwrap();
+    assert_eq!(sample.precision, Precision::High);
+    assert_eq!(sample.next_interval_ms, 5_000);
+
+    let sample = sampler.sample(70_000, delay).unwrap();
+    assert_eq!(sample.precision, Precision::High);
+    assert_eq!(sample.next_interval_ms, 60_000);
+
+    let sample = sampler.sample(130_000, delay).unwrap();
+    assert_eq!(sample.precision, Precision::Low);
+
+    i2c.done();
+}
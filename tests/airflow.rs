@@ -0,0 +1,41 @@
+mod common;
+
+use common::measure;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use fixed::types::I16F16;
+use sht4x::{airflow::Anemometer, HeatingDuration, HeatingPower, Sht4x};
+
+fn pulse(baseline: u16, heated: u16) -> Vec<Transaction> {
+    [measure(0xfd, baseline, 29360), measure(0x2f, heated, 29360)].concat()
+}
+
+#[test]
+fn estimates_airflow_relative_to_still_air() {
+    let mut expectations = Vec::new();
+    // Roughly 25 °C heated up to 65 °C in still air.
+    expectations.extend(pulse(26214, 41194));
+    // Roughly 25 °C heated up to 45 °C with airflow.
+    expectations.extend(pulse(26214, 33704));
+    // No airflow at all.
+    expectations.extend(pulse(26214, 41194));
+    let mut i2c = Mock::new(&expectations);
+    let mut anemometer = Anemometer::new(Sht4x::new(i2c.clone()));
+
+    let calibration = anemometer
+        .calibrate(HeatingPower::Medium, HeatingDuration::Long, &mut NoopDelay)
+        .unwrap();
+    let tolerance = I16F16::from_num(0.01);
+    assert!((calibration.temperature_rise - I16F16::from_num(40)).abs() < tolerance);
+
+    let estimate = anemometer.estimate(&calibration, &mut NoopDelay).unwrap();
+    assert!((estimate.temperature_rise - I16F16::from_num(20)).abs() < tolerance);
+    assert!((estimate.relative_airflow - I16F16::ONE).abs() < tolerance);
+
+    let estimate = anemometer.estimate(&calibration, &mut NoopDelay).unwrap();
+    assert_eq!(estimate.relative_airflow, I16F16::ZERO);
+
+    i2c.done();
+}
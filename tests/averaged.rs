@@ -0,0 +1,85 @@
+mod common;
+
+use common::measure;
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use sht4x::{Measurement, Precision, SensorData, Sht4x};
+
+#[test]
+fn averages_conversions() {
+    let mut expectations = Vec::new();
+    expectations.extend(measure(0xe0, 1000, 2000));
+    expectations.extend(measure(0xe0, 1003, 1990));
+    expectations.extend(measure(0xe0, 1002, 2001));
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4x::new(i2c.clone());
+
+    let averaged = sensor
+        .measure_averaged(3, Precision::Low, &mut NoopDelay)
+        .unwrap();
+
+    assert_eq!(averaged.count, 3);
+    assert_eq!(
+        averaged.measurement,
+        Measurement::from(SensorData {
+            temperature: 1002,
+            humidity: 1997,
+        })
+    );
+    assert_eq!(
+        averaged.min,
+        SensorData {
+            temperature: 1000,
+            humidity: 1990,
+        }
+    );
+    assert_eq!(
+        averaged.max,
+        SensorData {
+            temperature: 1003,
+            humidity: 2001,
+        }
+    );
+
+    i2c.done();
+}
+
+#[test]
+fn treats_zero_count_as_one() {
+    let expectations = measure(0xe0, 1000, 2000);
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4x::new(i2c.clone());
+
+    let averaged = sensor
+        .measure_averaged(0, Precision::Low, &mut NoopDelay)
+        .unwrap();
+
+    assert_eq!(averaged.count, 1);
+    assert_eq!(averaged.min, averaged.max);
+
+    i2c.done();
+}
+
+#[cfg(feature = "embedded-hal-async")]
+#[test]
+fn averages_conversions_async() {
+    use embassy_futures::block_on;
+    use sht4x::Sht4xAsync;
+
+    let mut expectations = Vec::new();
+    expectations.extend(measure(0xe0, 1000, 2000));
+    expectations.extend(measure(0xe0, 1002, 2002));
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4xAsync::new(i2c.clone());
+
+    let averaged = block_on(sensor.measure_averaged(2, Precision::Low, &mut NoopDelay)).unwrap();
+
+    assert_eq!(
+        averaged.measurement,
+        Measurement::from(SensorData {
+            temperature: 1001,
+            humidity: 2001,
+        })
+    );
+
+    i2c.done();
+}
@@ -0,0 +1,48 @@
+use core::cell::Cell;
+use embedded_hal::delay::DelayNs;
+use sht4x::ChunkedDelay;
+
+/// A delay recording the total time waited in nanoseconds.
+#[derive(Default)]
+struct RecordingDelay {
+    total_ns: u64,
+}
+
+impl DelayNs for RecordingDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.total_ns += u64::from(ns);
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.total_ns += u64::from(ms) * 1_000_000;
+    }
+}
+
+#[test]
+fn invokes_hook_between_chunks() {
+    let calls = Cell::new(0);
+    let mut delay = ChunkedDelay::new(RecordingDelay::default(), 100, || {
+        calls.set(calls.get() + 1)
+    });
+
+    delay.delay_ms(1100);
+
+    let (inner, _) = delay.destroy();
+    assert_eq!(inner.total_ns, 1_100_000_000);
+    assert_eq!(calls.get(), 10);
+}
+
+#[test]
+fn short_delay_does_not_invoke_hook() {
+    let calls = Cell::new(0);
+    let mut delay = ChunkedDelay::new(RecordingDelay::default(), 100, || {
+        calls.set(calls.get() + 1)
+    });
+
+    delay.delay_ms(9);
+    delay.delay_us(100_000);
+
+    let (inner, _) = delay.destroy();
+    assert_eq!(inner.total_ns, 109_000_000);
+    assert_eq!(calls.get(), 0);
+}
@@ -0,0 +1,52 @@
+//! Fixtures shared by the integration tests.
+
+// Not every test uses every fixture.
+#![allow(dead_code)]
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use sensirion_i2c::crc8;
+use sht4x::{Measurement, SensorData};
+
+/// Returns the response of a sensor transmitting the given words, each followed by its CRC.
+pub fn words(words: &[u16]) -> Vec<u8> {
+    words
+        .iter()
+        .flat_map(|word| {
+            let bytes = word.to_be_bytes();
+            [bytes[0], bytes[1], crc8::calculate(&bytes)]
+        })
+        .collect()
+}
+
+/// Returns the response to a measurement command with the given raw values.
+pub fn response(temperature: u16, humidity: u16) -> Vec<u8> {
+    words(&[temperature, humidity])
+}
+
+/// Returns the response to a serial number command.
+pub fn serial_number_response(serial_number: u32) -> Vec<u8> {
+    words(&[(serial_number >> 16) as u16, serial_number as u16])
+}
+
+/// Returns the transactions of the given command to a sensor at the default address answering
+/// with the given raw values.
+pub fn measure(command: u8, temperature: u16, humidity: u16) -> [Transaction; 2] {
+    [
+        Transaction::write(0x44, vec![command]),
+        Transaction::read(0x44, response(temperature, humidity)),
+    ]
+}
+
+/// Returns the transactions of a 200 mW heater pulse of 1 s which heats the sensor from roughly
+/// 25 °C and 50 %RH up to 65 °C and 10 %RH.
+pub fn heat() -> [Transaction; 2] {
+    measure(0x39, 41194, 8389)
+}
+
+/// Returns the measurement for the given raw values.
+pub fn measurement(temperature: u16, humidity: u16) -> Measurement {
+    Measurement::from(SensorData {
+        temperature,
+        humidity,
+    })
+}
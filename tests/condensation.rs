@@ -0,0 +1,133 @@
+mod common;
+
+use common::{heat, measure};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use sht4x::{
+    CondensationRecovery, CondensationRecoveryConfig, RecoveryEvent, RecoveryPhase, Sht4x,
+};
+
+#[test]
+fn recovers_with_heater_pulses() {
+    let saturated = u16::MAX;
+    let normal = 29360;
+    let mut expectations = Vec::new();
+    expectations.extend(measure(0xfd, 26214, saturated));
+    expectations.extend(measure(0xfd, 26214, saturated));
+    expectations.extend(heat());
+    expectations.extend(heat());
+    expectations.extend(measure(0xfd, 26214, normal));
+    expectations.extend(measure(0xfd, 26214, normal + 100));
+    let mut i2c = Mock::new(&expectations);
+
+    let config = CondensationRecoveryConfig {
+        trigger_after_ms: 1000,
+        pulses: 2,
+        pulse_interval_ms: 100,
+        cool_down_ms: 500,
+        settle_interval_ms: 1000,
+        ..Default::default()
+    };
+    let mut recovery = CondensationRecovery::new(Sht4x::new(i2c.clone()), config);
+    let delay = &mut NoopDelay;
+
+    let step = recovery.step(0, delay).unwrap();
+    assert!(step.measurement.is_some());
+    assert_eq!(step.event, None);
+
+    let step = recovery.step(1000, delay).unwrap();
+    assert!(step.measurement.is_some());
+    assert_eq!(step.event, Some(RecoveryEvent::Started));
+    assert_eq!(recovery.phase(), RecoveryPhase::Heating { pulses_left: 2 });
+
+    let step = recovery.step(1000, delay).unwrap();
+    assert_eq!(step.measurement, None);
+    assert!(step.heated.is_some());
+
+    // Waiting for the end of the 1100 ms heater pulse and the pulse interval.
+    let step = recovery.step(2100, delay).unwrap();
+    assert_eq!(step.measurement, None);
+    assert_eq!(step.heated, None);
+
+    let step = recovery.step(2200, delay).unwrap();
+    assert_eq!(step.measurement, None);
+    assert!(step.heated.is_some());
+    assert_eq!(recovery.phase(), RecoveryPhase::CoolingDown);
+
+    // Cooling down after the end of the heater pulse.
+    let step = recovery.step(3700, delay).unwrap();
+    assert_eq!(step.measurement, None);
+
+    let step = recovery.step(3800, delay).unwrap();
+    assert!(step.measurement.is_some());
+    assert_eq!(step.event, None);
+    assert_eq!(recovery.phase(), RecoveryPhase::Settling);
+
+    let step = recovery.step(4800, delay).unwrap();
+    assert!(step.measurement.is_some());
+    assert_eq!(step.event, Some(RecoveryEvent::Stabilised));
+    assert_eq!(recovery.phase(), RecoveryPhase::Monitoring);
+
+    i2c.done();
+}
+
+#[test]
+fn does_not_trigger_on_short_high_humidity() {
+    let mut expectations = Vec::new();
+    expectations.extend(measure(0xfd, 26214, u16::MAX));
+    expectations.extend(measure(0xfd, 26214, 29360));
+    expectations.extend(measure(0xfd, 26214, u16::MAX));
+    let mut i2c = Mock::new(&expectations);
+
+    let config = CondensationRecoveryConfig {
+        trigger_after_ms: 1000,
+        ..Default::default()
+    };
+    let mut recovery = CondensationRecovery::new(Sht4x::new(i2c.clone()), config);
+    let delay = &mut NoopDelay;
+
+    for now_ms in [0, 600, 1200] {
+        let step = recovery.step(now_ms, delay).unwrap();
+        assert_eq!(step.event, None);
+    }
+    assert_eq!(recovery.phase(), RecoveryPhase::Monitoring);
+
+    i2c.done();
+}
+
+#[test]
+fn does_not_stabilise_on_rapid_steps() {
+    let mut expectations = Vec::new();
+    expectations.extend(measure(0xfd, 26214, u16::MAX));
+    expectations.extend(heat());
+    for _ in 0..4 {
+        expectations.extend(measure(0xfd, 26214, 29360));
+    }
+    let mut i2c = Mock::new(&expectations);
+
+    let config = CondensationRecoveryConfig {
+        trigger_after_ms: 0,
+        pulses: 1,
+        cool_down_ms: 0,
+        settle_interval_ms: 1000,
+        ..Default::default()
+    };
+    let mut recovery = CondensationRecovery::new(Sht4x::new(i2c.clone()), config);
+    let delay = &mut NoopDelay;
+
+    let step = recovery.step(0, delay).unwrap();
+    assert_eq!(step.event, Some(RecoveryEvent::Started));
+    recovery.step(0, delay).unwrap();
+
+    // Identical readings in quick succession don't count as stabilised.
+    for now_ms in [1100, 1105, 1110] {
+        let step = recovery.step(now_ms, delay).unwrap();
+        assert!(step.measurement.is_some());
+        assert_eq!(step.event, None);
+    }
+    assert_eq!(recovery.phase(), RecoveryPhase::Settling);
+
+    let step = recovery.step(2100, delay).unwrap();
+    assert_eq!(step.event, Some(RecoveryEvent::Stabilised));
+
+    i2c.done();
+}
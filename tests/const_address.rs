@@ -0,0 +1,48 @@
+mod common;
+
+use common::serial_number_response;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use sht4x::{typestate, ConstAddress, Sht4x, Sht4xGroup};
+
+#[test]
+fn uses_const_address() {
+    let expectations = [
+        Transaction::write(0x46, vec![0x89]),
+        Transaction::read(0x46, serial_number_response(0x00010203)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor: Sht4x<_, NoopDelay, ConstAddress<0x46>> =
+        Sht4x::new_with_address(i2c.clone(), ConstAddress::new());
+
+    assert_eq!(sensor.serial_number(&mut NoopDelay), Ok(0x00010203));
+    assert_eq!(core::mem::size_of_val(&ConstAddress::<0x46>::new()), 0);
+
+    i2c.done();
+}
+
+#[test]
+fn wrappers_use_const_address() {
+    let expectations = [
+        Transaction::write(0x45, vec![0x89]),
+        Transaction::read(0x45, serial_number_response(0x00010203)),
+        Transaction::write(0x45, vec![0x89]),
+        Transaction::read(0x45, serial_number_response(0x00010203)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let sensor: Sht4x<_, NoopDelay, ConstAddress<0x45>> =
+        Sht4x::new_with_address(i2c.clone(), ConstAddress::new());
+
+    let mut group = Sht4xGroup::new([sensor]);
+    assert_eq!(group.serial_numbers(&mut NoopDelay), [Ok(0x00010203)]);
+
+    let [sensor] = group.destroy();
+    let idle = typestate::Sht4x::from(sensor);
+    let pending = idle.start_serial_number().ok().unwrap();
+    let (serial_number, _) = pending.wait_and_read(&mut NoopDelay).ok().unwrap();
+    assert_eq!(serial_number, 0x00010203);
+
+    i2c.done();
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "embassy")]
+
+mod common;
+
+use common::response;
+use embassy_futures::block_on;
+use embassy_sync::{blocking_mutex::raw::NoopRawMutex, pubsub::PubSubChannel};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use sht4x::{MeasurementTask, MeasurementTaskConfig, Precision, Reading, Sht4xAsync};
+
+const ADDRESS: u8 = 0x44;
+
+#[test]
+fn publishes_measurements() {
+    let expectations = [
+        Transaction::write(ADDRESS, vec![0xe0]),
+        Transaction::read(ADDRESS, response(0, u16::MAX)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let channel = PubSubChannel::<NoopRawMutex, Reading<ErrorKind>, 4, 1, 1>::new();
+    let mut subscriber = channel.subscriber().unwrap();
+
+    let config = MeasurementTaskConfig {
+        precision: Precision::Low,
+        ..Default::default()
+    };
+    let mut task = MeasurementTask::new(
+        Sht4xAsync::new(i2c.clone()),
+        NoopDelay,
+        channel.publisher().unwrap(),
+        config,
+    );
+    block_on(task.step());
+
+    let measurement = subscriber.try_next_message_pure().unwrap().unwrap();
+    assert_eq!(measurement.temperature_celsius(), -45);
+    assert_eq!(measurement.humidity_percent(), -6 + 125);
+
+    i2c.done();
+}
+
+#[test]
+fn soft_resets_after_consecutive_errors() {
+    let expectations = [
+        Transaction::write(ADDRESS, vec![0xfd]).with_error(ErrorKind::Other),
+        Transaction::write(ADDRESS, vec![0xfd]).with_error(ErrorKind::Other),
+        Transaction::write(ADDRESS, vec![0x94]),
+        Transaction::write(ADDRESS, vec![0xfd]),
+        Transaction::read(ADDRESS, response(0, 0)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let channel = PubSubChannel::<NoopRawMutex, Reading<ErrorKind>, 4, 1, 1>::new();
+    let mut subscriber = channel.subscriber().unwrap();
+
+    let config = MeasurementTaskConfig {
+        max_consecutive_errors: 2,
+        ..Default::default()
+    };
+    let mut task = MeasurementTask::new(
+        Sht4xAsync::new(i2c.clone()),
+        NoopDelay,
+        channel.publisher().unwrap(),
+        config,
+    );
+    for _ in 0..3 {
+        block_on(task.step());
+    }
+
+    assert!(subscriber.try_next_message_pure().unwrap().is_err());
+    assert!(subscriber.try_next_message_pure().unwrap().is_err());
+    assert!(subscriber.try_next_message_pure().unwrap().is_ok());
+
+    i2c.done();
+}
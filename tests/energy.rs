@@ -0,0 +1,48 @@
+use sht4x::{
+    HeatingDuration, HeatingPower, Operation, PowerBudget, Precision, SupplyModel, Timings,
+};
+
+#[test]
+fn estimates_charge_per_operation() {
+    let model = SupplyModel::TYPICAL;
+    let timings = Timings::MAXIMUM;
+
+    // 320 µA for 9 ms.
+    assert_eq!(
+        model.charge_nc(Operation::Measure(Precision::High), &timings),
+        2880
+    );
+    // 2880 nC at 3.3 V.
+    assert_eq!(
+        model.energy_uj(Operation::Measure(Precision::High), &timings),
+        9
+    );
+
+    // 320 µA for 1100 ms plus 200 mW at 3.3 V for 1000 ms.
+    let heated = Operation::HeatAndMeasure(HeatingPower::High, HeatingDuration::Long);
+    assert_eq!(model.charge_nc(heated, &timings), 352_000 + 60_606_000);
+
+    // The heater's current scales with the supply voltage.
+    let model = SupplyModel {
+        voltage_mv: 1800,
+        ..SupplyModel::TYPICAL
+    };
+    assert_eq!(model.charge_nc(heated, &timings), 352_000 + 33_057_000);
+}
+
+#[test]
+fn averages_current_over_period() {
+    let mut budget = PowerBudget::new(SupplyModel::TYPICAL, Timings::MAXIMUM);
+    budget.add(Operation::Measure(Precision::High), 1);
+
+    // 2880 nC within one second plus 80 nA for the remaining 991 ms.
+    assert_eq!(
+        budget.average_current_na(1000),
+        (2_880_000 + 80 * 991) / 1000
+    );
+    assert_eq!(budget.charge_nc(), 2880);
+
+    budget.add(Operation::Measure(Precision::Low), 10);
+    assert_eq!(budget.charge_nc(), 2880 + 10 * 640);
+    assert_eq!(budget.average_current_na(0), 0);
+}
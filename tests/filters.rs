@@ -0,0 +1,55 @@
+use fixed::types::I16F16;
+use sht4x::{ExponentialMovingAverage, Measurement, MedianFilter, MovingAverage, SensorData};
+
+fn raw(temperature: u16, humidity: u16) -> SensorData {
+    SensorData {
+        temperature,
+        humidity,
+    }
+}
+
+#[test]
+fn moving_average_over_window() {
+    let mut filter = MovingAverage::<SensorData, 3>::new();
+
+    assert_eq!(filter.update(raw(100, 1000)), raw(100, 1000));
+    assert_eq!(filter.update(raw(200, 2000)), raw(150, 1500));
+    assert_eq!(filter.update(raw(300, 3000)), raw(200, 2000));
+    // The first value drops out of the window.
+    assert_eq!(filter.update(raw(400, 4000)), raw(300, 3000));
+
+    filter.reset();
+    assert_eq!(filter.update(raw(10, 10)), raw(10, 10));
+}
+
+#[test]
+fn moving_average_of_measurements() {
+    let mut filter = MovingAverage::<Measurement, 2>::default();
+    let cold = Measurement::from(raw(0, 0));
+    let hot = Measurement::from(raw(u16::MAX, u16::MAX));
+
+    filter.update(cold);
+    let average = filter.update(hot);
+
+    assert_eq!(average.temperature_milli_celsius(), 42500);
+    assert_eq!(average.humidity_milli_percent(), 56500);
+}
+
+#[test]
+fn exponential_moving_average() {
+    let mut filter = ExponentialMovingAverage::<SensorData>::new(I16F16::from_num(0.25));
+
+    assert_eq!(filter.update(raw(1000, 1000)), raw(1000, 1000));
+    assert_eq!(filter.update(raw(2000, 1000)), raw(1250, 1000));
+    assert_eq!(filter.update(raw(2000, 1000)), raw(1438, 1000));
+}
+
+#[test]
+fn median_rejects_spikes() {
+    let mut filter = MedianFilter::<SensorData, 3>::new();
+
+    filter.update(raw(100, 1000));
+    assert_eq!(filter.update(raw(102, 1002)), raw(101, 1001));
+    assert_eq!(filter.update(raw(u16::MAX, 0)), raw(102, 1000));
+    assert_eq!(filter.update(raw(101, 1001)), raw(102, 1001));
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::response;
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTransaction},
+    i2c::{Mock, Transaction},
+};
+use sht4x::{Address, Error, Precision, Sht4x, Sht4xGroup};
+
+#[test]
+fn waits_once_for_all_sensors() {
+    let expectations = [
+        Transaction::write(0x44, vec![0xfd]),
+        Transaction::write(0x45, vec![0xfd]).with_error(ErrorKind::Other),
+        Transaction::write(0x46, vec![0xfd]),
+        Transaction::read(0x44, response(0, 0)),
+        Transaction::read(0x46, response(u16::MAX, u16::MAX)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(9)]);
+    let mut group = Sht4xGroup::new([
+        Sht4x::new_with_address(i2c.clone(), Address::Address0x44),
+        Sht4x::new_with_address(i2c.clone(), Address::Address0x45),
+        Sht4x::new_with_address(i2c.clone(), Address::Address0x46),
+    ]);
+
+    let [first, second, third] = group.measure(Precision::High, &mut delay);
+
+    assert_eq!(first.unwrap().temperature_celsius(), -45);
+    assert_eq!(second, Err(Error::I2c(ErrorKind::Other)));
+    assert_eq!(third.unwrap().temperature_celsius(), -45 + 175);
+
+    i2c.done();
+    delay.done();
+}
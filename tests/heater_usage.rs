@@ -0,0 +1,122 @@
+use fixed::types::I16F16;
+use sht4x::{HeaterUsage, HeatingDuration, HeatingPower, HEATER_USAGE_LEN};
+
+#[test]
+fn accumulates_on_time_and_energy() {
+    let mut usage = HeaterUsage::new();
+    usage.record(HeatingPower::High, HeatingDuration::Long);
+    usage.record(HeatingPower::High, HeatingDuration::Short);
+    usage.record(HeatingPower::Low, HeatingDuration::Long);
+
+    assert_eq!(usage.on_time_ms(HeatingPower::High), 1100);
+    assert_eq!(usage.on_time_ms(HeatingPower::Medium), 0);
+    assert_eq!(usage.total_on_time_ms(), 2100);
+    assert_eq!(usage.energy_mj(HeatingPower::High), 220);
+    assert_eq!(usage.total_energy_mj(), 240);
+}
+
+#[test]
+fn reports_budget_consumed() {
+    let mut usage = HeaterUsage::new();
+    for _ in 0..5 {
+        usage.record(HeatingPower::Medium, HeatingDuration::Long);
+    }
+
+    assert_eq!(usage.budget_consumed(100_000), I16F16::from_num(0.5));
+    assert_eq!(usage.budget_consumed(0), I16F16::MAX);
+}
+
+#[test]
+fn roundtrips_serialization() {
+    let mut usage = HeaterUsage::new();
+    usage.record(HeatingPower::Medium, HeatingDuration::Short);
+
+    let mut bytes = usage.to_bytes();
+    assert_eq!(HeaterUsage::from_bytes(&bytes), Some(usage));
+
+    bytes[5] ^= 0x01;
+    assert_eq!(HeaterUsage::from_bytes(&bytes), None);
+    assert_eq!(HeaterUsage::from_bytes(&[0xff; HEATER_USAGE_LEN]), None);
+}
+
+#[cfg(feature = "embedded-storage")]
+mod flash {
+    use core::convert::Infallible;
+    use embedded_storage::nor_flash::{ErrorType, NorFlash, ReadNorFlash};
+    use sht4x::{HeaterUsage, HeatingDuration, HeatingPower};
+
+    struct RamFlash([u8; 256]);
+
+    impl ErrorType for RamFlash {
+        type Error = Infallible;
+    }
+
+    impl ReadNorFlash for RamFlash {
+        const READ_SIZE: usize = 1;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            let offset = offset as usize;
+            bytes.copy_from_slice(&self.0[offset..offset + bytes.len()]);
+            Ok(())
+        }
+
+        fn capacity(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl NorFlash for RamFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = 128;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+            self.0[from as usize..to as usize].fill(0xff);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            let offset = offset as usize;
+            for (cell, byte) in self.0[offset..offset + bytes.len()].iter_mut().zip(bytes) {
+                *cell &= byte;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn stores_and_loads() {
+        let mut flash = RamFlash([0xff; 256]);
+        assert_eq!(HeaterUsage::load(&mut flash, 0), Ok(None));
+
+        let mut usage = HeaterUsage::new();
+        usage.record(HeatingPower::Low, HeatingDuration::Long);
+        usage.store(&mut flash, 0).unwrap();
+        usage.record(HeatingPower::Low, HeatingDuration::Long);
+        usage.store(&mut flash, 0).unwrap();
+        usage.record(HeatingPower::Low, HeatingDuration::Long);
+        usage.store(&mut flash, 0).unwrap();
+
+        assert_eq!(HeaterUsage::load(&mut flash, 0), Ok(Some(usage)));
+    }
+
+    #[test]
+    fn keeps_previous_usage_on_interrupted_store() {
+        let mut flash = RamFlash([0xff; 256]);
+        let mut usage = HeaterUsage::new();
+        usage.record(HeatingPower::High, HeatingDuration::Long);
+        usage.store(&mut flash, 0).unwrap();
+        usage.record(HeatingPower::High, HeatingDuration::Long);
+        usage.store(&mut flash, 0).unwrap();
+
+        // Power lost right after erasing the sector for the next store.
+        flash.erase(0, 128).unwrap();
+        assert_eq!(HeaterUsage::load(&mut flash, 0), Ok(Some(usage)));
+
+        // Power lost while writing the next store, leaving some bits unprogrammed.
+        let mut next = usage;
+        next.record(HeatingPower::High, HeatingDuration::Long);
+        next.store(&mut flash, 0).unwrap();
+        flash.0[24] = 0xff;
+        assert_eq!(HeaterUsage::load(&mut flash, 0), Ok(Some(usage)));
+    }
+}
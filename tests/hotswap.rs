@@ -0,0 +1,86 @@
+mod common;
+
+use common::{measure, serial_number_response};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use sht4x::{HotSwapMonitor, Precision, SensorReplaced, Sht4x};
+
+fn serial_number(serial_number: u32) -> [Transaction; 2] {
+    [
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(serial_number)),
+    ]
+}
+
+#[test]
+fn reports_replacement_after_failed_measurement() {
+    let mut expectations = vec![Transaction::write(0x44, vec![0x94])];
+    expectations.extend(serial_number(1));
+    expectations.extend(serial_number(1));
+    expectations.extend(measure(0xe0, 0x6666, 0x8000));
+    expectations.push(
+        Transaction::write(0x44, vec![0xe0])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+    );
+    expectations.extend(serial_number(2));
+    expectations.extend(measure(0xe0, 0x6666, 0x8000));
+    let mut i2c = Mock::new(&expectations);
+    let mut monitor = HotSwapMonitor::new(Sht4x::new(i2c.clone()), 0);
+
+    assert_eq!(monitor.init(&mut NoopDelay), Ok(1));
+    let (_, replaced) = monitor.measure(Precision::Low, &mut NoopDelay).unwrap();
+    assert_eq!(replaced, None);
+    assert!(monitor.measure(Precision::Low, &mut NoopDelay).is_err());
+    let (_, replaced) = monitor.measure(Precision::Low, &mut NoopDelay).unwrap();
+    assert_eq!(replaced, Some(SensorReplaced { old: 1, new: 2 }));
+    assert_eq!(monitor.serial_number(), Some(2));
+
+    i2c.done();
+}
+
+#[test]
+fn keeps_replacement_until_measurement_succeeds() {
+    let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    let mut expectations = vec![Transaction::write(0x44, vec![0x94])];
+    expectations.extend(serial_number(1));
+    expectations.extend(serial_number(1));
+    expectations.extend(measure(0xe0, 0x6666, 0x8000));
+    expectations.extend(serial_number(2));
+    expectations.push(Transaction::write(0x44, vec![0xe0]).with_error(nack));
+    expectations.extend(serial_number(2));
+    expectations.extend(measure(0xe0, 0x6666, 0x8000));
+    let mut i2c = Mock::new(&expectations);
+    let mut monitor = HotSwapMonitor::new(Sht4x::new(i2c.clone()), 1);
+
+    assert_eq!(monitor.init(&mut NoopDelay), Ok(1));
+    let (_, replaced) = monitor.measure(Precision::Low, &mut NoopDelay).unwrap();
+    assert_eq!(replaced, None);
+    assert!(monitor.measure(Precision::Low, &mut NoopDelay).is_err());
+    assert_eq!(monitor.serial_number(), Some(2));
+    let (_, replaced) = monitor.measure(Precision::Low, &mut NoopDelay).unwrap();
+    assert_eq!(replaced, Some(SensorReplaced { old: 1, new: 2 }));
+
+    i2c.done();
+}
+
+#[test]
+fn rechecks_periodically() {
+    let mut expectations = vec![];
+    expectations.extend(serial_number(1));
+    expectations.extend(measure(0xe0, 0x6666, 0x8000));
+    expectations.extend(measure(0xe0, 0x6666, 0x8000));
+    expectations.extend(serial_number(1));
+    expectations.extend(measure(0xe0, 0x6666, 0x8000));
+    let mut i2c = Mock::new(&expectations);
+    let mut monitor = HotSwapMonitor::new(Sht4x::new(i2c.clone()), 2);
+
+    for _ in 0..3 {
+        let (_, replaced) = monitor.measure(Precision::Low, &mut NoopDelay).unwrap();
+        assert_eq!(replaced, None);
+    }
+
+    i2c.done();
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use common::serial_number_response;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use sht4x::{Error, Sht4x};
+
+#[test]
+fn returns_serial_number() {
+    let expectations = [
+        Transaction::write(0x44, vec![0x94]),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x0badcafe)),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x0badcafe)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4x::new(i2c.clone());
+
+    assert_eq!(sensor.init(&mut NoopDelay), Ok(0x0badcafe));
+
+    i2c.done();
+}
+
+#[test]
+fn detects_absent_sensor() {
+    let expectations = [Transaction::write(0x44, vec![0x94])
+        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))];
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4x::new(i2c.clone());
+
+    assert_eq!(sensor.init(&mut NoopDelay), Err(Error::NotPresent));
+
+    i2c.done();
+}
+
+#[test]
+fn detects_inconsistent_serial_number() {
+    let expectations = [
+        Transaction::write(0x44, vec![0x94]),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x0badcafe)),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x0badc0de)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4x::new(i2c.clone());
+
+    assert_eq!(
+        sensor.init(&mut NoopDelay),
+        Err(Error::InconsistentResponse)
+    );
+
+    i2c.done();
+}
@@ -0,0 +1,52 @@
+mod common;
+
+use common::measurement;
+use fixed::types::I16F16;
+use sht4x::{KalmanConfig, KalmanFilter, Precision};
+
+#[test]
+fn first_measurement_initializes_estimate() {
+    let mut filter = KalmanFilter::new(KalmanConfig::default());
+    assert_eq!(filter.estimate(), None);
+
+    let first = measurement(26214, 29360);
+    let estimate = filter.update(first, Precision::High);
+
+    assert_eq!(estimate.measurement, first);
+    // A third of the repeatability of 0.04 °C and 0.08 %RH.
+    let tolerance = I16F16::from_num(0.0001);
+    assert!((estimate.temperature_uncertainty - I16F16::from_num(0.04 / 3.0)).abs() < tolerance);
+    assert!((estimate.humidity_uncertainty - I16F16::from_num(0.08 / 3.0)).abs() < tolerance);
+    assert_eq!(filter.estimate(), Some(estimate));
+}
+
+#[test]
+fn weights_measurements_by_precision() {
+    let config = KalmanConfig {
+        temperature_process_noise: I16F16::ZERO,
+        humidity_process_noise: I16F16::ZERO,
+    };
+    let first = measurement(26214, 29360);
+    let second = measurement(26314, 29460);
+
+    let mut high = KalmanFilter::new(config);
+    high.update(first, Precision::High);
+    let high = high.update(second, Precision::High);
+
+    let mut low = KalmanFilter::new(config);
+    low.update(first, Precision::High);
+    let low = low.update(second, Precision::Low);
+
+    // Equally precise measurements get averaged.
+    let midway = (first.temperature_celsius() + second.temperature_celsius()) / 2;
+    let tolerance = I16F16::from_num(0.001);
+    assert!((high.measurement.temperature_celsius() - midway).abs() < tolerance);
+
+    // A low-precision measurement moves the estimate less.
+    assert!(low.measurement.temperature_celsius() < high.measurement.temperature_celsius());
+    assert!(low.measurement.humidity_percent() < high.measurement.humidity_percent());
+
+    // Uncertainty shrinks with every measurement.
+    assert!(high.temperature_uncertainty < I16F16::from_num(0.04 / 3.0));
+    assert!(low.temperature_uncertainty < I16F16::from_num(0.04 / 3.0));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::serial_number_response;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use sht4x::{I2cMux, Sht4x, MUX_CHANNELS};
+
+const MUX_ADDRESS: u8 = 0x70;
+
+#[test]
+fn selects_channel_only_when_switching() {
+    let expectations = [
+        Transaction::write(MUX_ADDRESS, vec![1 << 2]),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x1234)),
+        Transaction::write(MUX_ADDRESS, vec![1 << 5]),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x5678)),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x5678)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mux = I2cMux::new(i2c.clone(), MUX_ADDRESS);
+    let mut first = Sht4x::new(mux.channel(2).unwrap());
+    let mut second = Sht4x::new(mux.channel(5).unwrap());
+
+    assert_eq!(first.serial_number(&mut NoopDelay), Ok(0x1234));
+    assert_eq!(second.serial_number(&mut NoopDelay), Ok(0x5678));
+    assert_eq!(second.serial_number(&mut NoopDelay), Ok(0x5678));
+
+    i2c.done();
+}
+
+#[test]
+fn deselects_other_multiplexers() {
+    const OTHER_MUX_ADDRESS: u8 = 0x71;
+    let expectations = [
+        Transaction::write(OTHER_MUX_ADDRESS, vec![0]),
+        Transaction::write(MUX_ADDRESS, vec![1 << 2]),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x1234)),
+        Transaction::write(MUX_ADDRESS, vec![0]),
+        Transaction::write(OTHER_MUX_ADDRESS, vec![1 << 2]),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x5678)),
+        Transaction::write(OTHER_MUX_ADDRESS, vec![0]),
+        Transaction::write(MUX_ADDRESS, vec![1 << 2]),
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x1234)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mux = I2cMux::new_multiple(i2c.clone(), [MUX_ADDRESS, OTHER_MUX_ADDRESS]);
+    let mut first = Sht4x::new(mux.channel(2).unwrap());
+    let mut second = Sht4x::new(mux.channel(MUX_CHANNELS + 2).unwrap());
+
+    assert!(mux.channel(2 * MUX_CHANNELS).is_none());
+    assert_eq!(first.serial_number(&mut NoopDelay), Ok(0x1234));
+    assert_eq!(second.serial_number(&mut NoopDelay), Ok(0x5678));
+    assert_eq!(first.serial_number(&mut NoopDelay), Ok(0x1234));
+
+    i2c.done();
+}
+
+#[test]
+fn rejects_nonexistent_channel() {
+    let mux = I2cMux::new(Mock::new(&[]), MUX_ADDRESS);
+
+    assert!(mux.channel(8).is_none());
+
+    mux.destroy().done();
+}
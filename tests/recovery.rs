@@ -0,0 +1,54 @@
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    digital::{Mock as PinMock, State, Transaction},
+};
+use sht4x::{recover_bus, BusRecoveryError};
+
+fn clock_pulse() -> [Transaction; 2] {
+    [Transaction::set(State::Low), Transaction::set(State::High)]
+}
+
+#[test]
+fn clocks_until_sda_is_released() {
+    let mut scl_expectations = vec![Transaction::set(State::High)];
+    scl_expectations.extend(clock_pulse());
+    scl_expectations.extend(clock_pulse());
+    // The STOP condition toggles SCL the same way.
+    scl_expectations.extend(clock_pulse());
+    let sda_expectations = [
+        Transaction::set(State::High),
+        Transaction::get(State::Low),
+        Transaction::get(State::Low),
+        Transaction::get(State::High),
+        Transaction::set(State::Low),
+        Transaction::set(State::High),
+    ];
+    let mut scl = PinMock::new(&scl_expectations);
+    let mut sda = PinMock::new(&sda_expectations);
+
+    assert_eq!(recover_bus(&mut scl, &mut sda, &mut NoopDelay), Ok(()));
+
+    scl.done();
+    sda.done();
+}
+
+#[test]
+fn gives_up_after_nine_pulses() {
+    let mut scl_expectations = vec![Transaction::set(State::High)];
+    let mut sda_expectations = vec![Transaction::set(State::High)];
+    for _ in 0..9 {
+        scl_expectations.extend(clock_pulse());
+        sda_expectations.push(Transaction::get(State::Low));
+    }
+    sda_expectations.push(Transaction::get(State::Low));
+    let mut scl = PinMock::new(&scl_expectations);
+    let mut sda = PinMock::new(&sda_expectations);
+
+    assert_eq!(
+        recover_bus(&mut scl, &mut sda, &mut NoopDelay),
+        Err(BusRecoveryError::SdaStuckLow)
+    );
+
+    scl.done();
+    sda.done();
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{response, serial_number_response};
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use fixed::types::I16F16;
+use sht4x::{
+    Address, Aggregation, Error, Precision, RedundancyError, RedundantSensors, Sht4x, Sht4xGroup,
+    Tolerance, Vote,
+};
+
+const ADDRESSES: [Address; 3] = [
+    Address::Address0x44,
+    Address::Address0x45,
+    Address::Address0x46,
+];
+
+const TOLERANCE: Tolerance = Tolerance {
+    temperature: I16F16::const_from_int(1),
+    humidity: I16F16::const_from_int(3),
+};
+
+fn group(i2c: &Mock) -> Sht4xGroup<Mock, NoopDelay, 3> {
+    Sht4xGroup::new(ADDRESSES.map(|address| Sht4x::new_with_address(i2c.clone(), address)))
+}
+
+fn serial_number_expectations() -> Vec<Transaction> {
+    // Every sensor reports its address as serial number.
+    let addresses = ADDRESSES.map(u8::from);
+    let writes = addresses
+        .iter()
+        .map(|address| Transaction::write(*address, vec![0x89]));
+    let reads = addresses
+        .iter()
+        .map(|address| Transaction::read(*address, serial_number_response((*address).into())));
+    writes.chain(reads).collect()
+}
+
+fn measure_expectations(data: [(u16, u16); 3]) -> Vec<Transaction> {
+    let addresses = ADDRESSES.map(u8::from);
+    let writes = addresses
+        .iter()
+        .map(|address| Transaction::write(*address, vec![0xfd]));
+    let reads = addresses
+        .iter()
+        .zip(data)
+        .map(|(address, (t, h))| Transaction::read(*address, response(t, h)));
+    writes.chain(reads).collect()
+}
+
+#[test]
+fn votes_out_deviating_sensor() {
+    // Raw values for about 25 °C, 25.1 °C, and 30 °C at about 50 %RH.
+    let mut expectations = serial_number_expectations();
+    expectations.extend(measure_expectations([
+        (26_214, 31_457),
+        (26_252, 31_457),
+        (28_086, 31_457),
+    ]));
+    let mut i2c = Mock::new(&expectations);
+    let mut sensors =
+        RedundantSensors::new(group(&i2c), Aggregation::Median, TOLERANCE, &mut NoopDelay).unwrap();
+
+    let aggregated = sensors.measure(Precision::High, &mut NoopDelay).unwrap();
+
+    assert_eq!(
+        aggregated.votes,
+        [Vote::Accepted, Vote::Accepted, Vote::VotedOut]
+    );
+    assert_eq!(aggregated.voted_out().collect::<Vec<_>>(), vec![0x46]);
+    assert_eq!(
+        aggregated.measurement.temperature_milli_celsius() / 100,
+        250
+    );
+
+    i2c.done();
+}
+
+#[test]
+fn no_consensus_between_two_remaining_sensors() {
+    let mut measurements = measure_expectations([(26_214, 31_457), (0, 0), (28_086, 31_457)]);
+    measurements[1] = Transaction::write(0x45, vec![0xfd]).with_error(ErrorKind::Other);
+    measurements.remove(4);
+    let mut expectations = serial_number_expectations();
+    expectations.extend(measurements);
+    let mut i2c = Mock::new(&expectations);
+    let mut sensors =
+        RedundantSensors::new(group(&i2c), Aggregation::Median, TOLERANCE, &mut NoopDelay).unwrap();
+
+    let result = sensors.measure(Precision::High, &mut NoopDelay);
+
+    assert_eq!(result, Err(RedundancyError::NoConsensus));
+
+    i2c.done();
+}
+
+#[test]
+fn fails_on_unreadable_serial_number() {
+    let mut expectations = serial_number_expectations();
+    expectations[1] = Transaction::write(0x45, vec![0x89]).with_error(ErrorKind::Other);
+    expectations.remove(4);
+    let mut i2c = Mock::new(&expectations);
+
+    let result = RedundantSensors::new(group(&i2c), Aggregation::Median, TOLERANCE, &mut NoopDelay);
+
+    assert_eq!(result.err(), Some(Error::I2c(ErrorKind::Other)));
+
+    i2c.done();
+}
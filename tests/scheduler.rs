@@ -0,0 +1,105 @@
+mod common;
+
+use common::{heat, measure};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use sht4x::{MeasurementScheduler, ScheduledMeasurement, SchedulerConfig, Sht4x};
+
+fn config() -> SchedulerConfig {
+    SchedulerConfig {
+        interval_ms: 1000,
+        heating_interval_ms: 5000,
+        cool_down_ms: 2000,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn interleaves_heater_cycles_and_suppresses_cool_down() {
+    let mut expectations = Vec::new();
+    expectations.extend(measure(0xfd, 26214, 29360));
+    expectations.extend(measure(0xfd, 26214, 29360));
+    expectations.extend(heat());
+    expectations.extend(measure(0xfd, 26214, 29360));
+    let mut i2c = Mock::new(&expectations);
+    let mut scheduler = MeasurementScheduler::new(Sht4x::new(i2c.clone()), config());
+    let delay = &mut NoopDelay;
+
+    let first = scheduler.tick(0, delay).unwrap().unwrap();
+    assert!(!first.is_heated());
+    assert_eq!(scheduler.tick(500, delay).unwrap(), None);
+    assert_eq!(scheduler.next_due_ms(), 1000);
+    assert!(matches!(
+        scheduler.tick(1000, delay).unwrap(),
+        Some(ScheduledMeasurement::Normal(_))
+    ));
+
+    let heated = scheduler.tick(5000, delay).unwrap().unwrap();
+    assert!(heated.is_heated());
+    // The cool-down starts after the heater cycle of 1100 ms.
+    assert_eq!(scheduler.next_due_ms(), 8100);
+
+    // Normal measurements during cool-down get suppressed.
+    assert_eq!(scheduler.tick(5000, delay).unwrap(), None);
+    assert_eq!(scheduler.tick(6000, delay).unwrap(), None);
+    assert_eq!(scheduler.tick(7000, delay).unwrap(), None);
+
+    assert!(matches!(
+        scheduler.tick(8100, delay).unwrap(),
+        Some(ScheduledMeasurement::Normal(_))
+    ));
+    assert_eq!(scheduler.next_due_ms(), 9000);
+
+    i2c.done();
+}
+
+#[test]
+fn cool_down_starts_after_heater_cycle() {
+    let mut expectations = Vec::new();
+    expectations.extend(heat());
+    expectations.extend(measure(0xfd, 26214, 29360));
+    let mut i2c = Mock::new(&expectations);
+    let config = SchedulerConfig {
+        cool_down_ms: 100,
+        ..config()
+    };
+    let mut scheduler = MeasurementScheduler::new(Sht4x::new(i2c.clone()), config);
+    let delay = &mut NoopDelay;
+
+    let heated = scheduler.tick(5000, delay).unwrap().unwrap();
+    assert!(heated.is_heated());
+
+    // The heater cycle of 1100 ms is over but the sensor has not cooled down for 100 ms yet.
+    assert_eq!(scheduler.tick(6100, delay).unwrap(), None);
+    assert_eq!(scheduler.next_due_ms(), 7100);
+    assert!(matches!(
+        scheduler.tick(7100, delay).unwrap(),
+        Some(ScheduledMeasurement::Normal(_))
+    ));
+
+    i2c.done();
+}
+
+#[cfg(feature = "embedded-hal-async")]
+mod asynchronous {
+    use super::*;
+    use embassy_futures::block_on;
+    use sht4x::{MeasurementSchedulerAsync, Sht4xAsync};
+
+    #[test]
+    fn ticks_async_driver() {
+        let mut expectations = Vec::new();
+        expectations.extend(measure(0xfd, 26214, 29360));
+        expectations.extend(heat());
+        let mut i2c = Mock::new(&expectations);
+        let mut scheduler = MeasurementSchedulerAsync::new(Sht4xAsync::new(i2c.clone()), config());
+        let delay = &mut NoopDelay;
+
+        let normal = block_on(scheduler.tick(0, delay)).unwrap().unwrap();
+        assert!(!normal.is_heated());
+        assert_eq!(block_on(scheduler.tick(500, delay)).unwrap(), None);
+        let heated = block_on(scheduler.tick(5000, delay)).unwrap().unwrap();
+        assert!(heated.is_heated());
+
+        i2c.done();
+    }
+}
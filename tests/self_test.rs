@@ -0,0 +1,54 @@
+mod common;
+
+use common::{heat, measure};
+use embedded_hal_mock::eh1::{delay::NoopDelay, i2c::Mock};
+use fixed::types::I16F16;
+use sht4x::{HeatingDuration, HeatingPower, SelfTestLimits, Sht4x};
+
+#[test]
+fn passes_on_heater_reaction() {
+    // Roughly 25 °C and 50 %RH heated up to 65 °C and 10 %RH.
+    let expectations = [measure(0xfd, 26214, 29360), heat()].concat();
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4x::new(i2c.clone());
+
+    let limits = SelfTestLimits::for_heating(HeatingPower::High, HeatingDuration::Long);
+    let report = sensor
+        .self_test(
+            HeatingPower::High,
+            HeatingDuration::Long,
+            limits,
+            &mut NoopDelay,
+        )
+        .unwrap();
+
+    assert!(report.passed());
+    assert!(report.temperature_rise() > I16F16::from_num(39));
+    assert!(report.humidity_drop() > I16F16::from_num(39));
+
+    i2c.done();
+}
+
+#[test]
+fn fails_without_heater_reaction() {
+    let expectations = [measure(0xfd, 26214, 29360), measure(0x39, 26214, 29360)].concat();
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4x::new(i2c.clone());
+
+    let limits = SelfTestLimits::for_heating(HeatingPower::High, HeatingDuration::Long);
+    let report = sensor
+        .self_test(
+            HeatingPower::High,
+            HeatingDuration::Long,
+            limits,
+            &mut NoopDelay,
+        )
+        .unwrap();
+
+    assert!(!report.passed());
+    assert!(!report.temperature_passed());
+    assert!(report.humidity_passed());
+    assert_eq!(report.temperature_rise(), I16F16::ZERO);
+
+    i2c.done();
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{response, serial_number_response};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTransaction},
+    i2c::{Mock, Transaction},
+};
+use sht4x::{HeatingDuration, HeatingPower, Precision, Sht4xWithDelay};
+
+#[test]
+fn measures_with_owned_delay() {
+    let expectations = [
+        Transaction::write(0x44, vec![0xfd]),
+        Transaction::read(0x44, response(u16::MAX, 0)),
+    ];
+    let i2c = Mock::new(&expectations);
+    let delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(9)]);
+    let mut sensor = Sht4xWithDelay::new(i2c, delay);
+
+    let measurement = sensor.measure(Precision::High).unwrap();
+    assert_eq!(measurement.temperature_celsius(), -45 + 175);
+    assert_eq!(measurement.humidity_percent(), -6);
+
+    let (mut i2c, mut delay) = sensor.destroy();
+    i2c.done();
+    delay.done();
+}
+
+#[test]
+fn heats_with_owned_delay() {
+    let expectations = [
+        Transaction::write(0x44, vec![0x32]),
+        Transaction::read(0x44, response(0, u16::MAX)),
+    ];
+    let i2c = Mock::new(&expectations);
+    let delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(110)]);
+    let mut sensor = Sht4xWithDelay::new(i2c, delay);
+
+    let measurement = sensor
+        .heat_and_measure(HeatingPower::High, HeatingDuration::Short)
+        .unwrap();
+    assert_eq!(measurement.temperature_celsius(), -45);
+    assert_eq!(measurement.humidity_percent(), -6 + 125);
+
+    let (mut i2c, mut delay) = sensor.destroy();
+    i2c.done();
+    delay.done();
+}
+
+#[test]
+fn reads_serial_number_with_owned_delay() {
+    let expectations = [
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x12345678)),
+    ];
+    let i2c = Mock::new(&expectations);
+    let delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(1)]);
+    let mut sensor = Sht4xWithDelay::new(i2c, delay);
+
+    assert_eq!(sensor.serial_number(), Ok(0x12345678));
+
+    let (mut i2c, mut delay) = sensor.destroy();
+    i2c.done();
+    delay.done();
+}
+
+#[test]
+fn destroy_returns_bus_and_delay() {
+    let i2c = Mock::new(&[]);
+    let delay = CheckedDelay::new(&[]);
+    let sensor = Sht4xWithDelay::new(i2c, delay);
+
+    let (mut i2c, mut delay) = sensor.destroy();
+    i2c.done();
+    delay.done();
+}
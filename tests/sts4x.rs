@@ -0,0 +1,32 @@
+mod common;
+
+use common::words;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use sht4x::{Precision, Sts4x, TemperatureMeasurement};
+
+#[test]
+fn from_min_max_data() {
+    let min = TemperatureMeasurement::from(0);
+    let max = TemperatureMeasurement::from(u16::MAX);
+
+    assert_eq!(min.temperature_milli_celsius(), -45000);
+    assert_eq!(max.temperature_milli_celsius(), -45000 + 175000);
+}
+
+#[test]
+fn reads_temperature_only_response() {
+    let expectations = [
+        Transaction::write(0x44, vec![0xf6]),
+        Transaction::read(0x44, words(&[0xffff])),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sts4x::new(i2c.clone());
+
+    let measurement = sensor.measure(Precision::Medium, &mut NoopDelay).unwrap();
+    assert_eq!(measurement.temperature_celsius(), -45 + 175);
+
+    i2c.done();
+}
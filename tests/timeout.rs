@@ -0,0 +1,58 @@
+#![cfg(feature = "embedded-hal-async")]
+
+mod common;
+
+use common::serial_number_response;
+use core::future::pending;
+use embassy_futures::block_on;
+use embedded_hal::i2c::{ErrorKind, ErrorType, Operation};
+use embedded_hal_async::i2c::I2c;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock, Transaction},
+};
+use sht4x::{Error, Precision, Sht4xAsync, Timeout};
+
+/// An I2C bus whose transactions never complete.
+struct StuckI2c;
+
+impl ErrorType for StuckI2c {
+    type Error = ErrorKind;
+}
+
+impl I2c for StuckI2c {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        _operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        pending().await
+    }
+}
+
+#[test]
+fn stuck_bus_times_out() {
+    let mut sensor = Sht4xAsync::new(StuckI2c);
+    let mut timeout = Timeout::new(NoopDelay, 10);
+
+    let result =
+        block_on(sensor.measure_with_timeout(Precision::High, &mut NoopDelay, &mut timeout));
+
+    assert_eq!(result, Err(Error::Timeout));
+}
+
+#[test]
+fn working_bus_completes() {
+    let expectations = [
+        Transaction::write(0x44, vec![0x89]),
+        Transaction::read(0x44, serial_number_response(0x12345678)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mut sensor = Sht4xAsync::new(i2c.clone());
+    let mut timeout = Timeout::new(NoopDelay, 10);
+
+    let serial = block_on(sensor.serial_number_with_timeout(&mut NoopDelay, &mut timeout));
+
+    assert_eq!(serial, Ok(0x12345678));
+    i2c.done();
+}
@@ -0,0 +1,34 @@
+mod common;
+
+use common::response;
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, Transaction as DelayTransaction},
+    i2c::{Mock, Transaction},
+};
+use sht4x::{Precision, Sht4x, Timings};
+
+#[test]
+fn margin_rounds_up() {
+    let timings = Timings::TYPICAL.with_margin_percent(20);
+
+    assert_eq!(timings.measure_high_precision_ms, 9);
+    assert_eq!(timings.measure_low_precision_ms, 3);
+    assert_eq!(timings.heat_long_ms, 1200);
+    assert_eq!(timings.heat_short_ms, 120);
+}
+
+#[test]
+fn driver_waits_configured_duration() {
+    let expectations = [
+        Transaction::write(0x44, vec![0xfd]),
+        Transaction::read(0x44, response(0, 0)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(7)]);
+    let mut sensor = Sht4x::new(i2c.clone()).with_timings(Timings::TYPICAL);
+
+    sensor.measure(Precision::High, &mut delay).unwrap();
+
+    i2c.done();
+    delay.done();
+}
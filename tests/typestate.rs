@@ -0,0 +1,46 @@
+mod common;
+
+use common::response;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::{CheckedDelay, NoopDelay, Transaction as DelayTransaction},
+    i2c::{Mock, Transaction},
+};
+use sht4x::{typestate::Sht4x, Error, Precision};
+
+#[test]
+fn read_can_be_retried_after_nack() {
+    let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    let expectations = [
+        Transaction::write(0x44, vec![0xfd]),
+        Transaction::read(0x44, vec![0; 6]).with_error(nack),
+        Transaction::read(0x44, response(0, 0)),
+    ];
+    let mut i2c = Mock::new(&expectations);
+    let idle: Sht4x<_, NoopDelay> = Sht4x::new(i2c.clone());
+
+    // The drivers don't implement Debug for NoopDelay. So use Option for unwrapping.
+    let measuring = idle.start_measurement(Precision::High).ok().unwrap();
+    assert_eq!(measuring.duration_ms(), 9);
+    let (measuring, error) = measuring.read().err().unwrap();
+    assert_eq!(error, Error::I2c(nack));
+    let (measurement, idle) = measuring.wait_and_read(&mut NoopDelay).ok().unwrap();
+    assert_eq!(measurement.temperature_celsius(), -45);
+
+    idle.destroy();
+    i2c.done();
+}
+
+#[test]
+fn abort_waits_for_pending_command() {
+    let expectations = [Transaction::write(0x44, vec![0xe0])];
+    let mut i2c = Mock::new(&expectations);
+    let mut delay = CheckedDelay::new(&[DelayTransaction::blocking_delay_ms(2)]);
+    let idle: Sht4x<_, CheckedDelay> = Sht4x::new(i2c.clone());
+
+    let measuring = idle.start_measurement(Precision::Low).ok().unwrap();
+    measuring.abort(&mut delay).destroy();
+
+    delay.done();
+    i2c.done();
+}